@@ -8,6 +8,7 @@ use pseudo_backtrace::{StackError, StackErrorExt};
 use thiserror::Error;
 
 #[derive(Debug, Error, StackError)]
+#[stack_error(constructors)]
 #[error("leaf layer: {message}")]
 struct LeafError {
     message: &'static str,
@@ -16,18 +17,8 @@ struct LeafError {
     location: &'static Location<'static>,
 }
 
-impl LeafError {
-    #[track_caller]
-    fn new(message: &'static str) -> Self {
-        Self {
-            message,
-            source: std::io::Error::other("device failure"),
-            location: Location::caller(),
-        }
-    }
-}
-
 #[derive(Debug, Error, StackError)]
+#[stack_error(constructors)]
 #[error("mid layer: {context}")]
 struct MidError {
     context: &'static str,
@@ -35,18 +26,8 @@ struct MidError {
     location: &'static Location<'static>,
 }
 
-impl MidError {
-    #[track_caller]
-    fn new(context: &'static str) -> Self {
-        Self {
-            context,
-            source: LeafError::new("leaf malfunction"),
-            location: Location::caller(),
-        }
-    }
-}
-
 #[derive(Debug, Error, StackError)]
+#[stack_error(constructors)]
 #[error("top layer: {operation}")]
 struct TopError {
     operation: &'static str,
@@ -54,19 +35,10 @@ struct TopError {
     location: &'static Location<'static>,
 }
 
-impl TopError {
-    #[track_caller]
-    fn new(operation: &'static str) -> Self {
-        Self {
-            operation,
-            source: MidError::new("mid initialization"),
-            location: Location::caller(),
-        }
-    }
-}
-
 fn main() {
-    let error = TopError::new("startup");
+    let leaf = LeafError::new("leaf malfunction", std::io::Error::other("device failure"));
+    let mid = MidError::new("mid initialization", leaf);
+    let error = TopError::new("startup", mid);
 
     println!("Display: {error}");
     println!("pseudo-backtrace frames:");
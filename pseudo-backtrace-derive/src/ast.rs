@@ -1,7 +1,7 @@
 use proc_macro2::Span;
-use syn::{Error, Generics, Ident, Index, Member, Result};
+use syn::{Error, Generics, Ident, Index, Member, Result, Visibility};
 
-use crate::attr::Attrs;
+use crate::attr::{self, Attrs, ContainerAttrs, FieldRef, StackErrorKind};
 
 pub enum Input<'a> {
     Struct(Struct<'a>),
@@ -28,6 +28,10 @@ pub enum ContainerKind {
 
 #[derive(Clone)]
 pub struct Struct<'a> {
+    pub attrs: ContainerAttrs<'a>,
+    /// Path to `pseudo_backtrace` used by generated code.
+    pub krate: syn::Path,
+    pub vis: &'a Visibility,
     pub ident: Ident,
     pub generics: &'a Generics,
     pub fields: Vec<Field<'a>>,
@@ -35,8 +39,17 @@ pub struct Struct<'a> {
 
 impl<'a> Struct<'a> {
     pub fn from_syn(input: &'a syn::DeriveInput, data: &'a syn::DataStruct) -> Result<Self> {
-        let fields = Field::from_fields(&data.fields, &input.ident)?;
+        let attrs = ContainerAttrs::from_syn(&input.attrs)?;
+        if let Some(flag) = &attrs.kind_enum {
+            return Err(Error::new(flag.span, "`kind` is only supported on an enum"));
+        }
+        let mut fields = Field::from_fields(&data.fields, &input.ident, &input.generics)?;
+        attrs.check_transparent(&fields)?;
+        attrs.apply_field_options(None, &mut fields)?;
         Ok(Self {
+            krate: attrs.crate_path(),
+            attrs,
+            vis: &input.vis,
             ident: input.ident.clone(),
             generics: &input.generics,
             fields,
@@ -46,6 +59,10 @@ impl<'a> Struct<'a> {
 
 #[derive(Clone)]
 pub struct Enum<'a> {
+    pub attrs: ContainerAttrs<'a>,
+    /// Path to `pseudo_backtrace` used by generated code.
+    pub krate: syn::Path,
+    pub vis: &'a Visibility,
     pub ident: Ident,
     pub generics: &'a Generics,
     pub variants: Vec<Variant<'a>>,
@@ -53,12 +70,29 @@ pub struct Enum<'a> {
 
 impl<'a> Enum<'a> {
     pub fn from_syn(input: &'a syn::DeriveInput, data: &'a syn::DataEnum) -> Result<Self> {
+        let attrs = ContainerAttrs::from_syn(&input.attrs)?;
+        if let Some(flag) = &attrs.from {
+            return Err(Error::new(
+                flag.span,
+                "`from` is not supported on an enum. place it on variants instead",
+            ));
+        }
+        if let Some(flag) = &attrs.transparent {
+            return Err(Error::new(
+                flag.span,
+                "`transparent` is not supported on an enum. place it on variants instead",
+            ));
+        }
         let variants = data
             .variants
             .iter()
-            .map(Variant::from_syn)
+            .map(|variant| Variant::from_syn(variant, &input.generics, &attrs))
             .collect::<Result<Vec<_>>>()?;
+        attrs.check_inherited_fields(&variants)?;
         Ok(Self {
+            krate: attrs.crate_path(),
+            attrs,
+            vis: &input.vis,
             ident: input.ident.clone(),
             generics: &input.generics,
             variants,
@@ -69,16 +103,27 @@ impl<'a> Enum<'a> {
 #[derive(Clone)]
 pub struct Variant<'a> {
     pub original: &'a syn::Variant,
+    pub attrs: ContainerAttrs<'a>,
     pub ident: Ident,
     pub fields: Vec<Field<'a>>,
 }
 
 impl<'a> Variant<'a> {
-    pub fn from_syn(input: &'a syn::Variant) -> Result<Self> {
+    pub fn from_syn(
+        input: &'a syn::Variant,
+        generics: &Generics,
+        enum_attrs: &ContainerAttrs<'a>,
+    ) -> Result<Self> {
+        let attrs = ContainerAttrs::from_syn(&input.attrs)?;
+        attrs.reject_type_only()?;
+        let mut fields = Field::from_fields(&input.fields, &input.ident, generics)?;
+        attrs.check_transparent(&fields)?;
+        attrs.apply_field_options(Some(enum_attrs), &mut fields)?;
         Ok(Self {
             original: input,
+            attrs,
             ident: input.ident.clone(),
-            fields: Field::from_fields(&input.fields, &input.ident)?,
+            fields,
         })
     }
 
@@ -95,16 +140,135 @@ impl<'a> Variant<'a> {
     }
 }
 
+impl<'a> ContainerAttrs<'a> {
+    /// Applies `location`, `next` and `kind` to the fields, falling back to the enum's options
+    /// for a variant.
+    ///
+    /// Fields named on the variant or struct itself must exist. An option inherited from the
+    /// enum is skipped for variants without that field, which keep the default field lookup.
+    fn apply_field_options(
+        &self,
+        inherited: Option<&ContainerAttrs<'a>>,
+        fields: &mut [Field<'a>],
+    ) -> Result<()> {
+        let pick =
+            |own: &Option<FieldRef<'a>>, parent: Option<&Option<FieldRef<'a>>>| match (own, parent)
+            {
+                (Some(own), _) => Some((own.clone(), true)),
+                (None, Some(Some(parent))) => Some((parent.clone(), false)),
+                _ => None,
+            };
+
+        if let Some((location, required)) =
+            pick(&self.location, inherited.map(|attrs| &attrs.location))
+            && let Some(field) = lookup(fields, &location, "location", required)?
+        {
+            field.attrs.location.get_or_insert(attr::Location {
+                original: location.original,
+                delegate: false,
+            });
+        }
+
+        if let Some((next, required)) = pick(&self.next, inherited.map(|attrs| &attrs.next))
+            && let Some(field) = lookup(fields, &next, "next", required)?
+        {
+            field.attrs.source.get_or_insert(attr::Source {
+                original: next.original,
+            });
+        }
+
+        if let Some(kind) = self.kind.or(inherited.and_then(|attrs| attrs.kind)) {
+            for field in fields.iter_mut() {
+                field.default_kind = Some(kind);
+            }
+        }
+
+        let krate = inherited.unwrap_or(self).crate_path();
+        for field in fields {
+            field.krate = krate.clone();
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a field named on the enum exists in at least one variant, since variants
+    /// without it silently keep the default lookup.
+    fn check_inherited_fields(&self, variants: &[Variant<'_>]) -> Result<()> {
+        let options = [(&self.location, "location"), (&self.next, "next")];
+        for (field, option) in options {
+            let Some(field) = field else { continue };
+            let exists = variants
+                .iter()
+                .any(|variant| variant.fields.iter().any(|f| f.member == field.member));
+            if !exists {
+                return Err(Error::new(
+                    field.span,
+                    format!(
+                        "unknown field `{}` in `{}`: no variant has it",
+                        field.describe(),
+                        option
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_transparent(&self, fields: &[Field<'_>]) -> Result<()> {
+        match &self.transparent {
+            Some(flag) if fields.len() != 1 => Err(Error::new(
+                flag.span,
+                "`transparent` needs exactly one field, the inner `StackError`",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn lookup<'f, 'a>(
+    fields: &'f mut [Field<'a>],
+    field: &FieldRef<'a>,
+    option: &str,
+    required: bool,
+) -> Result<Option<&'f mut Field<'a>>> {
+    match fields.iter_mut().find(|f| f.member == field.member) {
+        Some(found) if found.attrs.skip.is_some() => Err(Error::new(
+            field.span,
+            format!(
+                "field `{}` in `{}` is marked `#[stack_error(skip)]`",
+                field.describe(),
+                option
+            ),
+        )),
+        Some(found) => Ok(Some(found)),
+        None if required => Err(Error::new(
+            field.span,
+            format!("unknown field `{}` in `{}`", field.describe(), option),
+        )),
+        None => Ok(None),
+    }
+}
+
 #[derive(Clone)]
 pub struct Field<'a> {
     pub original: &'a syn::Field,
     pub attrs: Attrs<'a>,
     pub member: Member,
     pub ty: syn::Type,
+    /// Whether the type mentions one of the container's type parameters.
+    pub is_generic: bool,
+    /// Source kind set by a container `kind = ..` option.
+    pub default_kind: Option<StackErrorKind>,
+    /// Path to `pseudo_backtrace` set by the container's `crate = ..` option.
+    pub krate: syn::Path,
 }
 
 impl<'a> Field<'a> {
-    pub fn from_fields(fields: &'a syn::Fields, ident: &Ident) -> Result<Vec<Self>> {
+    pub fn from_fields(
+        fields: &'a syn::Fields,
+        ident: &Ident,
+        generics: &Generics,
+    ) -> Result<Vec<Self>> {
         if matches!(fields, syn::Fields::Unit) {
             return Err(Error::new_spanned(
                 ident,
@@ -112,14 +276,19 @@ impl<'a> Field<'a> {
             ));
         }
 
+        let params = generics.type_params().map(|p| p.ident.clone()).collect();
         fields
             .iter()
             .enumerate()
-            .map(|(i, f)| Self::from_syn(i, f))
+            .map(|(i, f)| Self::from_syn(i, f, &params))
             .collect()
     }
 
-    pub fn from_syn(i: usize, field: &'a syn::Field) -> Result<Self> {
+    pub fn from_syn(
+        i: usize,
+        field: &'a syn::Field,
+        params: &std::collections::BTreeSet<Ident>,
+    ) -> Result<Self> {
         let attrs = Attrs::from_syn(&field.attrs)?;
         let member = match &field.ident {
             Some(ident) => Member::Named(ident.clone()),
@@ -134,6 +303,9 @@ impl<'a> Field<'a> {
             attrs,
             member,
             ty: field.ty.clone(),
+            is_generic: crate::expand::mentions_param(&field.ty, params),
+            default_kind: None,
+            krate: syn::parse_quote!(::pseudo_backtrace),
         })
     }
 }
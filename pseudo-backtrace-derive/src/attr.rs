@@ -1,10 +1,13 @@
-use syn::{Attribute, Error, Ident, Result, parse::Parse};
+use proc_macro2::Span;
+use syn::{Attribute, Error, Ident, LitStr, Member, Result, parse::Parse};
 
 #[derive(Clone, Default)]
 pub struct Attrs<'a> {
     pub source: Option<Source<'a>>,
     pub location: Option<Location<'a>>,
     pub stack_error: Option<StackError<'a>>,
+    pub from: Option<FromField<'a>>,
+    pub skip: Option<Skip<'a>>,
 }
 
 impl<'a> Attrs<'a> {
@@ -22,25 +25,68 @@ impl<'a> Attrs<'a> {
             }
 
             if attr.path().is_ident("location") {
-                attr.meta.require_path_only()?;
+                let delegate = match &attr.meta {
+                    syn::Meta::Path(_) => false,
+                    _ => {
+                        let ident: Ident = attr.parse_args()?;
+                        if ident != "delegate" {
+                            return Err(Error::new_spanned(
+                                &ident,
+                                format!(
+                                    "invalid `#[location({})]` attribute. expected `delegate`",
+                                    ident
+                                ),
+                            ));
+                        }
+                        true
+                    }
+                };
                 if result.location.is_some() {
                     return Err(Error::new_spanned(
                         attr,
                         "duplicate `#[location]` attribute",
                     ));
                 }
-                result.location = Some(Location { original: attr });
+                result.location = Some(Location {
+                    original: attr,
+                    delegate,
+                });
+                continue;
+            }
+
+            if attr.path().is_ident("from") {
+                attr.meta.require_path_only()?;
+                if result.from.is_some() {
+                    return Err(Error::new_spanned(attr, "duplicate `#[from]` attribute"));
+                }
+                result.from = Some(FromField { original: attr });
                 continue;
             }
 
             if attr.path().is_ident("stack_error") {
-                let kind: StackErrorKind = attr.parse_args()?;
-                if result.stack_error.is_some() {
+                let ident: Ident = attr.parse_args()?;
+                if result.stack_error.is_some() || result.skip.is_some() {
                     return Err(Error::new_spanned(
                         attr,
                         "duplicate `#[stack_error(...)]` attribute",
                     ));
                 }
+                let kind = if ident == "std" {
+                    StackErrorKind::Std
+                } else if ident == "stacked" {
+                    StackErrorKind::Stacked
+                } else if ident == "skip" {
+                    result.skip = Some(Skip { original: attr });
+                    continue;
+                } else {
+                    return Err(Error::new_spanned(
+                        &ident,
+                        format!(
+                            "invalid `#[stack_error({})]` attribute. expected `std`, `stacked` or `skip`",
+                            ident
+                        ),
+                    ));
+                };
                 result.stack_error = Some(StackError {
                     original: attr,
                     kind,
@@ -49,6 +95,15 @@ impl<'a> Attrs<'a> {
             }
         }
 
+        if let Some(skip) = &result.skip
+            && (result.source.is_some() || result.location.is_some() || result.from.is_some())
+        {
+            return Err(Error::new_spanned(
+                skip.original,
+                "`#[stack_error(skip)]` cannot be combined with `#[source]`, `#[location]` or `#[from]`",
+            ));
+        }
+
         Ok(result)
     }
 }
@@ -59,16 +114,33 @@ pub struct Source<'a> {
     pub original: &'a Attribute,
 }
 
+/// `#[stack_error(skip)]`: the field is never picked as the location or the source.
+#[derive(Clone)]
+pub struct Skip<'a> {
+    pub original: &'a Attribute,
+}
+
 #[derive(Clone)]
 pub struct Location<'a> {
     #[allow(unused)]
     pub original: &'a Attribute,
+    /// `#[location(delegate)]`: the field is a `StackError` whose location is reported.
+    pub delegate: bool,
+}
+
+#[derive(Clone)]
+pub struct FromField<'a> {
+    #[allow(unused)]
+    pub original: &'a Attribute,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum StackErrorKind {
     Stacked,
     Std,
+    /// Not written by users: a concrete source the derive could not classify, resolved by the
+    /// compiler through the `pseudo_backtrace::private::AutoChain` fallback.
+    Auto,
 }
 
 impl Parse for StackErrorKind {
@@ -97,3 +169,333 @@ pub struct StackError<'a> {
     pub original: &'a Attribute,
     pub kind: StackErrorKind,
 }
+
+/// `#[stack_error(...)]` options placed on a struct, an enum or an enum variant.
+#[derive(Clone, Default)]
+pub struct ContainerAttrs<'a> {
+    pub display: Option<Display<'a>>,
+    pub impl_error: Option<Flag<'a>>,
+    pub from: Option<Flag<'a>>,
+    pub constructors: Option<Flag<'a>>,
+    pub transparent: Option<Flag<'a>>,
+    pub debug: Option<Flag<'a>>,
+    /// `partial_eq`: generate `PartialEq` that ignores the location field.
+    pub partial_eq: Option<Flag<'a>>,
+    /// `eq`: like `partial_eq`, and also generate `Eq`.
+    pub eq: Option<Flag<'a>>,
+    /// `boxed` or `boxed = Name`: generate a one-pointer wrapper around the type.
+    pub boxed: Option<Boxed<'a>>,
+    /// `max_size = N`: fail to compile when the type is larger than `N` bytes.
+    pub max_size: Option<MaxSize>,
+    /// `location = field`: the field providing `StackError::location`.
+    pub location: Option<FieldRef<'a>>,
+    /// `next = field`: the field providing `StackError::next`.
+    pub next: Option<FieldRef<'a>>,
+    /// `kind = std` or `kind = stacked`: the kind of a source field without its own attribute.
+    pub kind: Option<StackErrorKind>,
+    /// Bare `kind`: generate a field-less `Kind` enum and a `kind()` accessor.
+    pub kind_enum: Option<Flag<'a>>,
+    /// `crate = path`: where generated code finds `pseudo_backtrace`.
+    pub krate: Option<syn::Path>,
+}
+
+impl<'a> ContainerAttrs<'a> {
+    pub fn from_syn(inputs: &'a [Attribute]) -> Result<Self> {
+        let mut result = Self::default();
+
+        for attr in inputs {
+            if !attr.path().is_ident("stack_error") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("display") {
+                    let fmt: LitStr = meta.value()?.parse()?;
+                    if result.display.is_some() {
+                        return Err(meta.error("duplicate `display` option"));
+                    }
+                    result.display = Some(Display {
+                        original: attr,
+                        fmt,
+                    });
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("impl_error") {
+                    if result.impl_error.is_some() {
+                        return Err(meta.error("duplicate `impl_error` option"));
+                    }
+                    result.impl_error = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("from") {
+                    if result.from.is_some() {
+                        return Err(meta.error("duplicate `from` option"));
+                    }
+                    result.from = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("constructors") {
+                    if result.constructors.is_some() {
+                        return Err(meta.error("duplicate `constructors` option"));
+                    }
+                    result.constructors = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("transparent") {
+                    if result.transparent.is_some() {
+                        return Err(meta.error("duplicate `transparent` option"));
+                    }
+                    result.transparent = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("boxed") {
+                    let name = if meta.input.peek(syn::Token![=]) {
+                        Some(meta.value()?.parse::<Ident>()?)
+                    } else {
+                        None
+                    };
+                    if result.boxed.is_some() {
+                        return Err(meta.error("duplicate `boxed` option"));
+                    }
+                    result.boxed = Some(Boxed {
+                        flag: Flag::new(attr, &meta.path),
+                        name,
+                    });
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("max_size") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    let bytes = lit.base10_parse::<usize>()?;
+                    if result.max_size.is_some() {
+                        return Err(meta.error("duplicate `max_size` option"));
+                    }
+                    result.max_size = Some(MaxSize { lit, bytes });
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("debug") {
+                    if result.debug.is_some() {
+                        return Err(meta.error("duplicate `debug` option"));
+                    }
+                    result.debug = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("partial_eq") {
+                    if result.partial_eq.is_some() {
+                        return Err(meta.error("duplicate `partial_eq` option"));
+                    }
+                    result.partial_eq = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("eq") {
+                    if result.eq.is_some() {
+                        return Err(meta.error("duplicate `eq` option"));
+                    }
+                    result.eq = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("location") {
+                    let member: Member = meta.value()?.parse()?;
+                    if result.location.is_some() {
+                        return Err(meta.error("duplicate `location` option"));
+                    }
+                    result.location = Some(FieldRef::new(attr, member));
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("next") {
+                    let member: Member = meta.value()?.parse()?;
+                    if result.next.is_some() {
+                        return Err(meta.error("duplicate `next` option"));
+                    }
+                    result.next = Some(FieldRef::new(attr, member));
+                    return Ok(());
+                }
+
+                // `kind = std` sets the source kind, a bare `kind` asks for a `Kind` enum
+                if meta.path.is_ident("kind") && meta.input.peek(syn::Token![=]) {
+                    let kind: StackErrorKind = meta.value()?.parse()?;
+                    if result.kind.is_some() {
+                        return Err(meta.error("duplicate `kind` option"));
+                    }
+                    result.kind = Some(kind);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("kind") {
+                    if result.kind_enum.is_some() {
+                        return Err(meta.error("duplicate `kind` option"));
+                    }
+                    result.kind_enum = Some(Flag::new(attr, &meta.path));
+                    return Ok(());
+                }
+
+                // `crate = path` or `crate = "path"`, as serde and thiserror accept
+                if meta.path.is_ident("crate") {
+                    let value = meta.value()?;
+                    let path = if value.peek(LitStr) {
+                        value
+                            .parse::<LitStr>()?
+                            .parse_with(syn::Path::parse_mod_style)?
+                    } else {
+                        value.call(syn::Path::parse_mod_style)?
+                    };
+                    if result.krate.is_some() {
+                        return Err(meta.error("duplicate `crate` option"));
+                    }
+                    result.krate = Some(path);
+                    return Ok(());
+                }
+
+                let name = meta
+                    .path
+                    .get_ident()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_default();
+                Err(meta.error(format!(
+                    "invalid `#[stack_error({})]` attribute on a type or variant",
+                    name
+                )))
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// Rejects options that only make sense on the type itself when found on an enum variant.
+    pub fn reject_type_only(&self) -> Result<()> {
+        if let Some(flag) = &self.impl_error {
+            return Err(Error::new(
+                flag.span,
+                "`impl_error` is only supported on a struct or enum",
+            ));
+        }
+        if let Some(flag) = &self.constructors {
+            return Err(Error::new(
+                flag.span,
+                "`constructors` is only supported on a struct or enum",
+            ));
+        }
+        if let Some(flag) = &self.debug {
+            return Err(Error::new(
+                flag.span,
+                "`debug` is only supported on a struct or enum",
+            ));
+        }
+        if let Some(flag) = self.partial_eq.as_ref().or(self.eq.as_ref()) {
+            let option = if self.partial_eq.is_some() {
+                "partial_eq"
+            } else {
+                "eq"
+            };
+            return Err(Error::new(
+                flag.span,
+                format!("`{}` is only supported on a struct or enum", option),
+            ));
+        }
+        if let Some(boxed) = &self.boxed {
+            return Err(Error::new(
+                boxed.flag.span,
+                "`boxed` is only supported on a struct or enum",
+            ));
+        }
+        if let Some(max_size) = &self.max_size {
+            return Err(Error::new(
+                max_size.lit.span(),
+                "`max_size` is only supported on a struct or enum",
+            ));
+        }
+        if let Some(flag) = &self.kind_enum {
+            return Err(Error::new(flag.span, "`kind` is only supported on an enum"));
+        }
+        if let Some(path) = &self.krate {
+            return Err(Error::new_spanned(
+                path,
+                "`crate` is only supported on a struct or enum",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The path generated code uses for `pseudo_backtrace`, `::pseudo_backtrace` by default.
+    pub fn crate_path(&self) -> syn::Path {
+        self.krate
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(::pseudo_backtrace))
+    }
+}
+
+/// An option without a value, such as `#[stack_error(impl_error)]`.
+#[derive(Clone)]
+pub struct Flag<'a> {
+    #[allow(unused)]
+    pub original: &'a Attribute,
+    pub span: Span,
+}
+
+impl<'a> Flag<'a> {
+    fn new(original: &'a Attribute, path: &syn::Path) -> Self {
+        use syn::spanned::Spanned;
+        Self {
+            original,
+            span: path.span(),
+        }
+    }
+}
+
+/// `#[stack_error(boxed)]` or `#[stack_error(boxed = Name)]`.
+#[derive(Clone)]
+pub struct Boxed<'a> {
+    pub flag: Flag<'a>,
+    /// The wrapper name, defaulting to the type name without its `Inner` suffix.
+    pub name: Option<Ident>,
+}
+
+/// `#[stack_error(max_size = N)]`.
+#[derive(Clone)]
+pub struct MaxSize {
+    pub lit: syn::LitInt,
+    pub bytes: usize,
+}
+
+/// A field named by an option, such as `#[stack_error(location = meta)]` or `next = 0`.
+#[derive(Clone)]
+pub struct FieldRef<'a> {
+    pub original: &'a Attribute,
+    pub member: Member,
+    pub span: Span,
+}
+
+impl<'a> FieldRef<'a> {
+    fn new(original: &'a Attribute, member: Member) -> Self {
+        use syn::spanned::Spanned;
+        Self {
+            original,
+            span: member.span(),
+            member,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Display<'a> {
+    #[allow(unused)]
+    pub original: &'a Attribute,
+    pub fmt: LitStr,
+}
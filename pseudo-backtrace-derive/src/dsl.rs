@@ -0,0 +1,215 @@
+//! The `stack_errors!` macro: declares a set of error types in one place.
+//!
+//! Each declaration is lowered to a struct with a `source` and a `location` field and the
+//! `#[stack_error(...)]` attributes the derive understands, and is then expanded by the derive
+//! itself, so the generated `StackError` impls are exactly the ones `#[derive(StackError)]`
+//! produces.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{
+    Attribute, DeriveInput, Error, FieldsNamed, Generics, Ident, LitStr, Result, Token, Type,
+    Visibility,
+    parse::{Parse, ParseStream},
+    parse_quote, parse_quote_spanned,
+    spanned::Spanned,
+};
+
+use crate::{ast, expand};
+
+/// The whole input of `stack_errors!`.
+pub struct StackErrors {
+    errors: Vec<ErrorDef>,
+}
+
+impl Parse for StackErrors {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut errors = Vec::new();
+        while !input.is_empty() {
+            errors.push(input.parse()?);
+        }
+        Ok(Self { errors })
+    }
+}
+
+/// `#[attrs] vis struct Name<G> { fields }: kind Source => "display";`
+struct ErrorDef {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    struct_token: Token![struct],
+    ident: Ident,
+    generics: Generics,
+    fields: Option<FieldsNamed>,
+    source: Option<SourceDef>,
+    display: LitStr,
+}
+
+/// `: std Type`, `: stacked Type` or `: Type`.
+struct SourceDef {
+    kind: Option<Ident>,
+    ty: Type,
+}
+
+impl Parse for ErrorDef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let struct_token = input.parse()?;
+        let ident = input.parse()?;
+        let generics = input.parse()?;
+        let fields = if input.peek(syn::token::Brace) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let source = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if !input.peek(Token![=>]) {
+            return Err(input.error("expected `=> \"display message\"`"));
+        }
+        input.parse::<Token![=>]>()?;
+        let display = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(Self {
+            attrs,
+            vis,
+            struct_token,
+            ident,
+            generics,
+            fields,
+            source,
+            display,
+        })
+    }
+}
+
+impl Parse for SourceDef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // `std` starts a path as well, as in `std::io::Error`
+        let kind = if input.peek(Ident) && !input.peek2(Token![::]) {
+            let fork = input.fork();
+            let ident: Ident = fork.parse()?;
+            if ident == "std" || ident == "stacked" {
+                input.parse::<Ident>()?;
+                Some(ident)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        let ty = input.parse()?;
+        Ok(Self { kind, ty })
+    }
+}
+
+impl ErrorDef {
+    /// Lowers the declaration to the struct `#[derive(StackError)]` would be given.
+    fn to_derive_input(&self) -> Result<DeriveInput> {
+        let mut fields = match &self.fields {
+            Some(fields) => fields.clone(),
+            None => FieldsNamed {
+                brace_token: Default::default(),
+                named: Default::default(),
+            },
+        };
+        for field in &fields.named {
+            let Some(ident) = &field.ident else { continue };
+            if ident == "location" {
+                return Err(Error::new(
+                    ident.span(),
+                    "the `location` field is generated by `stack_errors!`",
+                ));
+            }
+            if ident == "source" {
+                return Err(Error::new(
+                    ident.span(),
+                    "declare the source after the fields, as in `Name { .. }: Source => \"..\"`",
+                ));
+            }
+        }
+
+        if let Some(SourceDef { kind, ty }) = &self.source {
+            let kind = kind.iter();
+            // `From` could only fill the other fields with `Default`
+            let from = fields.named.is_empty().then(|| quote! { #[from] });
+            fields.named.push(parse_quote_spanned! {ty.span()=>
+                #from
+                #(#[stack_error(#kind)])*
+                source: #ty
+            });
+        }
+        fields.named.push(parse_quote_spanned! {self.ident.span()=>
+            location: &'static ::core::panic::Location<'static>
+        });
+
+        let display = &self.display;
+        let mut attrs = self.attrs.clone();
+        attrs.push(parse_quote! {
+            #[stack_error(display = #display, impl_error, constructors)]
+        });
+
+        Ok(DeriveInput {
+            attrs,
+            vis: self.vis.clone(),
+            ident: self.ident.clone(),
+            generics: self.generics.clone(),
+            data: syn::Data::Struct(syn::DataStruct {
+                struct_token: self.struct_token,
+                fields: syn::Fields::Named(fields),
+                semi_token: None,
+            }),
+        })
+    }
+
+    fn expand(&self) -> Result<TokenStream> {
+        let mut input = self.to_derive_input()?;
+        let (impls, debug) = match ast::Input::from_input(&input)? {
+            ast::Input::Struct(s) => {
+                let debug = s.attrs.debug.is_none();
+                (expand::expand(ast::Input::Struct(s))?, debug)
+            }
+            ast::Input::Enum(_) => unreachable!("`stack_errors!` only declares structs"),
+        };
+
+        // the helper attributes belong to the derive, which is not attached to the item
+        input
+            .attrs
+            .retain(|attr| !attr.path().is_ident("stack_error"));
+        if debug {
+            input.attrs.insert(0, parse_quote!(#[derive(Debug)]));
+        }
+        if let syn::Data::Struct(data) = &mut input.data {
+            for field in data.fields.iter_mut() {
+                field.attrs.retain(|attr| !is_helper_attribute(attr));
+            }
+        }
+
+        Ok(quote! {
+            #input
+            #impls
+        })
+    }
+}
+
+fn is_helper_attribute(attr: &Attribute) -> bool {
+    ["source", "stack_error", "location", "from"]
+        .iter()
+        .any(|name| attr.path().is_ident(name))
+}
+
+pub fn expand(input: StackErrors) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    for error in &input.errors {
+        match error.expand() {
+            Ok(expanded) => expanded.to_tokens(&mut tokens),
+            Err(error) => error.into_compile_error().to_tokens(&mut tokens),
+        }
+    }
+    tokens
+}
@@ -1,10 +1,11 @@
 use proc_macro2::Span;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{Error, Ident, Result};
 
 use crate::{
     ast::{Enum, Field, Input, Struct, Variant},
-    attr::StackErrorKind,
+    attr::{Boxed, MaxSize, StackErrorKind},
+    fmt::FmtTrait,
 };
 
 pub fn expand(input: Input<'_>) -> Result<proc_macro2::TokenStream> {
@@ -19,100 +20,583 @@ impl Field<'_> {
         is_located_error(&self.ty)
     }
 
+    /// Whether this location field reports the location of another `StackError`.
+    fn is_location_delegate(&self) -> bool {
+        self.attrs.location.as_ref().is_some_and(|l| l.delegate) || self.is_located_error()
+    }
+
+    /// Builds the `StackError::location` body for this location field, `value` being a reference to it.
+    fn location_expr(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if self.attrs.location.as_ref().is_some_and(|l| l.delegate) {
+            let inner = self.as_stacked(value);
+            quote! { #inner.location() }
+        } else {
+            let krate = respan(&self.krate, self.span());
+            let ty = &self.ty;
+            quote_spanned! {self.span()=> <#ty as #krate::private::AsLocation>::as_location(#value) }
+        }
+    }
+
+    /// The span of the field type, where the derive reports a type that does not fit the field's role.
+    fn span(&self) -> Span {
+        syn::spanned::Spanned::span(&self.ty)
+    }
+
+    /// The type a source field reaches through deref: the field type without `Option`, `Box`,
+    /// `Arc`, `Rc` and references, with an inferred lifetime on trait objects.
+    fn target_type(&self) -> syn::Type {
+        let mut ty = peel_wrappers(&self.ty).clone();
+        if let syn::Type::TraitObject(object) = &mut ty
+            && !object
+                .bounds
+                .iter()
+                .any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)))
+        {
+            object.bounds.push(syn::parse_quote!('_));
+        }
+        ty
+    }
+
+    /// Dereferences `reference`, a reference to the field or to the value in its `Option`, down to
+    /// the target type.
+    ///
+    /// The derefs are spelled out because `&Box<dyn Error>` would unsize rather than deref.
+    fn deref_target(&self, reference: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let ty = option_inner_type(&self.ty).unwrap_or(&self.ty);
+        match deref_depth(ty) {
+            0 => reference,
+            depth => {
+                let derefs = (0..=depth).map(|_| quote! { * });
+                quote! { &#(#derefs)*(#reference) }
+            }
+        }
+    }
+
+    /// Builds `&dyn StackError` from `reference`, like [Field::deref_target].
+    ///
+    /// The call is spanned on the field type, so a type that is not a `StackError` is reported there.
+    fn as_stacked(&self, reference: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        self.call_with_target("as_stacked", reference)
+    }
+
+    /// Builds `&dyn Error` from `reference`, like [Field::as_stacked].
+    fn as_std(&self, reference: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        self.call_with_target("as_std", reference)
+    }
+
+    /// Calls `function` from the private module with the target type and the dereferenced `reference`.
+    fn call_with_target(
+        &self,
+        function: &str,
+        reference: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let krate = respan(&self.krate, self.span());
+        let function = Ident::new(function, self.span());
+        let ty = self.target_type();
+        let value = self.deref_target(reference);
+        let path = quote_spanned! {self.span()=> #krate::private::#function::<#ty> };
+        quote! { #path(#value) }
+    }
+
+    /// The kind given by `#[stack_error(..)]` or the container's `kind`, or else inferred from the type.
     fn stack_error_kind(&self) -> StackErrorKind {
-        self.attrs
-            .stack_error
-            .as_ref()
-            .map(|f| f.kind)
-            .unwrap_or(StackErrorKind::Stacked)
+        match (&self.attrs.stack_error, self.default_kind) {
+            (Some(attr), _) => attr.kind,
+            (None, Some(kind)) => kind,
+            (None, None) if self.is_generic => StackErrorKind::Stacked,
+            (None, None) => infer_source_kind(&self.ty),
+        }
+    }
+
+    /// Builds `StackError::location` of a `transparent` inner field.
+    fn transparent_location(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let inner = self.as_stacked(quote! { &#value });
+        quote! { #inner.location() }
+    }
+
+    /// Builds `StackError::next` of a `transparent` inner field, skipping the inner layer itself.
+    fn transparent_next(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let inner = self.as_stacked(quote! { &#value });
+        quote! { #inner.next() }
+    }
+
+    /// Builds `Error::source` of a `transparent` inner field.
+    fn transparent_source(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let inner = self.as_stacked(quote! { &#value });
+        quote! { #inner.source() }
+    }
+
+    /// Builds the expression that records the caller into this location field.
+    ///
+    /// `option` names the derive option that needs it, for the error message.
+    fn caller_location(&self, option: &str) -> Result<proc_macro2::TokenStream> {
+        if self.is_location_delegate() {
+            return Err(Error::new_spanned(
+                self.original,
+                format!(
+                    "`{}` cannot fill a location delegated to another `StackError` that is not the source",
+                    option
+                ),
+            ));
+        }
+        let krate = &self.krate;
+        Ok(quote! { #krate::private::FromCaller::from_caller() })
+    }
+
+    /// Builds the `StackError::next` body for this source field, `value` being the field itself.
+    fn next_expr(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let krate = &self.krate;
+        let kind = self.stack_error_kind();
+        let is_option = option_inner_type(&self.ty).is_some();
+        // `reference` is a reference to the field, or to the value in its `Option`
+        let chain = |reference: proc_macro2::TokenStream| match kind {
+            StackErrorKind::Stacked => {
+                let inner = self.as_stacked(reference);
+                quote! { #krate::Chain::Stacked(#inner) }
+            }
+            StackErrorKind::Std => {
+                let inner = self.as_std(reference);
+                quote! { #krate::Chain::Std(#inner) }
+            }
+            StackErrorKind::Auto => {
+                let spanned = respan(krate, self.span());
+                let ty = self.target_type();
+                let auto_chain =
+                    quote_spanned! {self.span()=> #spanned::private::AutoChain::<#ty> };
+                let value = self.deref_target(reference);
+                // spanned on the field so an unresolvable source is reported there
+                let method = quote_spanned! {self.span()=> auto_chain };
+                quote! {
+                    {
+                        use #krate::private::{AutoStacked as _, AutoStd as _};
+                        (&#auto_chain(#value)).#method()
+                    }
+                }
+            }
+        };
+        if is_option {
+            let chain = chain(quote! { __s });
+            quote! { #value.as_ref().map(|__s| #chain) }
+        } else {
+            let chain = chain(quote! { &#value });
+            quote! { ::core::option::Option::Some(#chain) }
+        }
+    }
+
+    /// Whether `partial_eq` compares this field with its own `PartialEq`.
+    ///
+    /// Std sources and `dyn StackError` rarely implement it, so those are compared by their chain.
+    fn eq_by_value(&self, is_source: bool) -> bool {
+        let ty = option_inner_type(&self.ty).unwrap_or(&self.ty);
+        !is_source
+            || (self.stack_error_kind() == StackErrorKind::Stacked
+                && !matches!(peel_wrappers(ty), syn::Type::TraitObject(_)))
+    }
+
+    /// Builds the `partial_eq` comparison of this field, `a` and `b` being references to it.
+    fn eq_expr(
+        &self,
+        is_source: bool,
+        a: proc_macro2::TokenStream,
+        b: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        if self.eq_by_value(is_source) {
+            return quote! { ::core::cmp::PartialEq::eq(#a, #b) };
+        }
+
+        let krate = &self.krate;
+        let inner = option_inner_type(&self.ty);
+        let compare = |a: proc_macro2::TokenStream, b: proc_macro2::TokenStream| match self
+            .stack_error_kind()
+        {
+            StackErrorKind::Stacked => {
+                let (a, b) = (self.as_stacked(a), self.as_stacked(b));
+                quote! { #krate::private::stack_eq(#a, #b) }
+            }
+            StackErrorKind::Std => {
+                let (a, b) = (self.as_std(a), self.as_std(b));
+                quote! { #krate::private::std_eq(#a, #b) }
+            }
+            StackErrorKind::Auto => {
+                let spanned = respan(krate, self.span());
+                let ty = self.target_type();
+                let auto_eq = quote_spanned! {self.span()=> #spanned::private::AutoEq::<#ty> };
+                let (a, b) = (self.deref_target(a), self.deref_target(b));
+                let method = quote_spanned! {self.span()=> auto_eq };
+                quote! {
+                    {
+                        use #krate::private::{AutoPartialEq as _, AutoStdEq as _};
+                        (&#auto_eq(#a, #b)).#method()
+                    }
+                }
+            }
+        };
+        if inner.is_some() {
+            let compare = compare(quote! { __a }, quote! { __b });
+            quote! {
+                match (#a, #b) {
+                    (::core::option::Option::Some(__a), ::core::option::Option::Some(__b)) => #compare,
+                    (::core::option::Option::None, ::core::option::Option::None) => true,
+                    _ => false,
+                }
+            }
+        } else {
+            compare(a, b)
+        }
+    }
+
+    /// Builds the `Error::source` body for this source field, `value` being the field itself.
+    fn source_expr(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let as_dyn = |reference: proc_macro2::TokenStream| match self.stack_error_kind() {
+            StackErrorKind::Stacked => self.as_stacked(reference),
+            StackErrorKind::Std => self.as_std(reference),
+            // an inferred source is a concrete sized type, so `Error` is reachable either way
+            StackErrorKind::Auto => self.call_with_target("as_source", reference),
+        };
+        if option_inner_type(&self.ty).is_some() {
+            let inner = as_dyn(quote! { __s });
+            quote! {
+                #value
+                    .as_ref()
+                    .map(|__s| -> &(dyn ::core::error::Error + 'static) { #inner })
+            }
+        } else {
+            let inner = as_dyn(quote! { &#value });
+            quote! {
+                {
+                    let __s: &(dyn ::core::error::Error + 'static) = #inner;
+                    ::core::option::Option::Some(__s)
+                }
+            }
+        }
     }
 }
 
 impl Struct<'_> {
+    fn transparent_field(&self) -> Option<&Field<'_>> {
+        self.attrs.transparent.as_ref().map(|_| &self.fields[0])
+    }
+
     fn location_fn(&self) -> Result<proc_macro2::TokenStream> {
+        if let Some(inner) = self.transparent_field() {
+            let member = &inner.member;
+            let body = inner.transparent_location(quote! { self.#member });
+            return Ok(quote! {
+                fn location(&self) -> &'static ::core::panic::Location<'static> {
+                    #body
+                }
+            });
+        }
+
         let location = find_location(&self.fields, self.ident.span())?;
         let location_member = location.member.clone();
+        let body = location.location_expr(quote! { &self.#location_member });
+
+        Ok(quote! {
+            fn location(&self) -> &'static ::core::panic::Location<'static> {
+                #body
+            }
+        })
+    }
 
-        let body = if location.is_located_error() {
-            quote! { ::pseudo_backtrace::StackError::location(&self.#location_member) }
+    fn next_fn(&self) -> Result<proc_macro2::TokenStream> {
+        let krate = &self.krate;
+        let body = if let Some(inner) = self.transparent_field() {
+            let member = &inner.member;
+            inner.transparent_next(quote! { self.#member })
+        } else if let Some(source) = find_source(&self.fields)? {
+            let member = source.member.clone();
+            source.next_expr(quote! { self.#member })
         } else {
-            quote! { self.#location_member }
+            quote! { ::core::option::Option::None }
         };
 
         Ok(quote! {
-            fn location(&self) -> &'static ::core::panic::Location<'static> {
+            fn next<'pseudo_backtrace>(&'pseudo_backtrace self) -> ::core::option::Option<#krate::Chain<'pseudo_backtrace>> {
                 #body
             }
         })
     }
+}
 
-    fn next_fn(&self) -> Result<proc_macro2::TokenStream> {
-        let source = find_source(&self.fields)?;
+impl Struct<'_> {
+    fn display_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
 
-        let body = if let Some(source) = source {
-            let member = source.member.clone();
-            let kind = source.stack_error_kind();
-            let is_option = option_inner_type(&source.ty).is_some();
-            match (is_option, kind) {
-                (true, StackErrorKind::Stacked) => {
-                    quote! {
-                        self.#member
-                            .as_ref()
-                            .map(|__s| ::pseudo_backtrace::Chain::Stacked(__s.as_dyn_stack_error()))
+        let Some(display) = &self.attrs.display else {
+            // like a transparent variant, a transparent struct forwards to the inner error
+            let (Some(inner), Some(_)) = (self.transparent_field(), &self.attrs.impl_error) else {
+                return Ok(None);
+            };
+            let member = &inner.member;
+            let where_clause = fmt_where_clause(
+                self.generics,
+                where_clause,
+                &[(inner.ty.clone(), FmtTrait::Display)],
+            );
+            return Ok(Some(quote! {
+                impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                    fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        ::core::fmt::Display::fmt(&self.#member, __formatter)
                     }
                 }
-                (true, StackErrorKind::Std) => {
-                    quote! {
-                        self.#member
-                            .as_ref()
-                            .map(|__s| ::pseudo_backtrace::Chain::Std(__s.as_dyn_std_error()))
-                    }
+            }));
+        };
+        let formatted = display.expand(&self.fields)?;
+        let pattern = formatted.pattern();
+        let fmt = &formatted.fmt;
+
+        let where_clause = fmt_where_clause(self.generics, where_clause, &formatted.uses);
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let Self #pattern = self;
+                    ::core::write!(__formatter, #fmt)
                 }
-                (false, StackErrorKind::Stacked) => {
-                    quote! {
-                        ::core::option::Option::Some(::pseudo_backtrace::Chain::Stacked(
-                            self.#member.as_dyn_stack_error(),
-                        ))
+            }
+        }))
+    }
+}
+
+impl Struct<'_> {
+    fn debug_impl(&self) -> Option<proc_macro2::TokenStream> {
+        self.attrs.debug.as_ref()?;
+
+        let krate = &self.krate;
+        let (pattern, body) = structural_debug(&self.ident, &self.fields);
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let mut tracker = BoundsTracker::new(self.generics, &self.krate);
+        tracker.collect_struct(self);
+        let where_clause = debug_where_clause(
+            self.generics,
+            where_clause,
+            &self.fields,
+            &tracker,
+            self.attrs.impl_error.is_some(),
+        );
+
+        Some(quote! {
+            impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    if __formatter.alternate() {
+                        let Self #pattern = self;
+                        #body
+                    } else {
+                        #krate::private::debug_chain(self, __formatter)
                     }
                 }
-                (false, StackErrorKind::Std) => {
-                    quote! {
-                        ::core::option::Option::Some(::pseudo_backtrace::Chain::Std(
-                            self.#member.as_dyn_std_error(),
-                        ))
-                    }
+            }
+        })
+    }
+
+    fn partial_eq_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        if self.attrs.partial_eq.is_none() && self.attrs.eq.is_none() {
+            return Ok(None);
+        }
+
+        let compared = eq_fields(
+            &self.fields,
+            self.transparent_field().is_some(),
+            self.ident.span(),
+        )?;
+        let comparisons = compared.iter().map(|(f, is_source)| {
+            let member = &f.member;
+            f.eq_expr(
+                *is_source,
+                quote! { &self.#member },
+                quote! { &other.#member },
+            )
+        });
+
+        let ident = &self.ident;
+        let impl_eq = eq_impl(ident, self.generics, self.attrs.eq.is_some(), &compared);
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let where_clause = eq_where_clause(self.generics, where_clause, &compared, false);
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::cmp::PartialEq for #ident #ty_generics #where_clause {
+                fn eq(&self, other: &Self) -> bool {
+                    true #(&& #comparisons)*
                 }
             }
+
+            #impl_eq
+        }))
+    }
+
+    fn error_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        if self.attrs.impl_error.is_none() {
+            return Ok(None);
+        }
+
+        let body = if let Some(inner) = self.transparent_field() {
+            let member = &inner.member;
+            inner.transparent_source(quote! { self.#member })
+        } else if let Some(source) = find_source(&self.fields)? {
+            let member = source.member.clone();
+            source.source_expr(quote! { self.#member })
         } else {
             quote! { ::core::option::Option::None }
         };
 
-        Ok(quote! {
-            fn next<'pseudo_backtrace>(&'pseudo_backtrace self) -> ::core::option::Option<::pseudo_backtrace::Chain<'pseudo_backtrace>> {
-                use ::pseudo_backtrace::private::AsDynStdError as _;
-                use ::pseudo_backtrace::private::AsDynStackError as _;
-                #body
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let mut tracker = BoundsTracker::new(self.generics, &self.krate);
+        tracker.collect_struct(self);
+        let where_clause = tracker.make_error_where_clause(where_clause);
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::error::Error for #ident #ty_generics #where_clause {
+                fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                    #body
+                }
             }
-        })
+        }))
+    }
+}
+
+impl Struct<'_> {
+    fn conversion_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let wants_from =
+            self.attrs.from.is_some() || self.fields.iter().any(|f| f.attrs.from.is_some());
+        if !wants_from {
+            return Ok(None);
+        }
+
+        let (source_ty, body) = from_body(
+            quote! { Self },
+            &self.fields,
+            self.transparent_field().is_some(),
+            self.ident.span(),
+        )?;
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::convert::From<#source_ty> for #ident #ty_generics #where_clause {
+                #[track_caller]
+                fn from(source: #source_ty) -> Self {
+                    #body
+                }
+            }
+        }))
+    }
+
+    fn size_assertion(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let Some(max_size) = &self.attrs.max_size else {
+            return Ok(None);
+        };
+        let parts = self
+            .fields
+            .iter()
+            .map(|f| (member_name(&f.member), vec![f.ty.clone()]))
+            .collect::<Vec<_>>();
+        size_assertion(&self.ident, self.generics, max_size, "field", &parts).map(Some)
+    }
+
+    fn boxed_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let Some(boxed) = &self.attrs.boxed else {
+            return Ok(None);
+        };
+
+        let wants_from =
+            self.attrs.from.is_some() || self.fields.iter().any(|f| f.attrs.from.is_some());
+        let mut from_types = Vec::new();
+        if wants_from {
+            let (source_ty, _) = from_body(
+                quote! { Self },
+                &self.fields,
+                self.transparent_field().is_some(),
+                self.ident.span(),
+            )?;
+            from_types.push(source_ty);
+        }
+
+        boxed_wrapper(
+            boxed,
+            self.vis,
+            &self.ident,
+            self.generics,
+            &from_types,
+            false,
+            &self.krate,
+        )
+        .map(Some)
+    }
+}
+
+impl Struct<'_> {
+    fn constructors_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        if self.attrs.constructors.is_none() {
+            return Ok(None);
+        }
+
+        let ident = &self.ident;
+        let doc = format!(
+            "Creates a new [`{}`], recording the caller as its location.",
+            ident
+        );
+        let new = constructor(
+            self.vis,
+            &quote::format_ident!("new"),
+            quote! { Self },
+            &self.fields,
+            self.transparent_field().is_some(),
+            self.ident.span(),
+            &doc,
+        )?;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        Ok(Some(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #new
+            }
+        }))
     }
 }
 
 fn expand_struct(input: Struct<'_>) -> Result<proc_macro2::TokenStream> {
     let location_fn = input.location_fn()?;
     let next_fn = input.next_fn()?;
+    let display_impl = input.display_impl()?;
+    let debug_impl = input.debug_impl();
+    let error_impl = input.error_impl()?;
+    let from_impl = input.conversion_impl()?;
+    let constructors_impl = input.constructors_impl()?;
+    let boxed_impl = input.boxed_impl()?;
+    let size_assertion = input.size_assertion()?;
+    let partial_eq_impl = input.partial_eq_impl()?;
 
     let ident = input.ident.clone();
+    let krate = &input.krate;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let mut tracker = BoundsTracker::new(input.generics);
-    tracker.collect(&input.fields);
-    let where_clause = tracker.make_where_clause(where_clause);
+    let mut tracker = BoundsTracker::new(input.generics, &input.krate);
+    tracker.collect_struct(&input);
+    // `StackError: Error`, so the impl must be at least as strict as a generated `Error` impl
+    let where_clause = if input.attrs.impl_error.is_some() {
+        tracker.make_error_where_clause(where_clause)
+    } else {
+        tracker.make_where_clause(where_clause)
+    };
 
     Ok(quote! {
-        impl #impl_generics ::pseudo_backtrace::StackError for #ident #ty_generics #where_clause {
+        impl #impl_generics #krate::StackError for #ident #ty_generics #where_clause {
            #location_fn
            #next_fn
         }
+
+        #display_impl
+        #debug_impl
+        #error_impl
+        #partial_eq_impl
+        #from_impl
+        #constructors_impl
+        #boxed_impl
+        #size_assertion
     })
 }
 
@@ -160,8 +644,19 @@ impl Variant<'_> {
         }
     }
 
+    fn transparent_field(&self) -> Option<&Field<'_>> {
+        self.attrs.transparent.as_ref().map(|_| &self.fields[0])
+    }
+
     fn location_body(&self) -> Result<proc_macro2::TokenStream> {
         let variant_ident = self.ident.clone();
+        if let Some(inner) = self.transparent_field() {
+            let binding = quote::format_ident!("__stack_error_inner");
+            let pattern = self.make_pattern(inner, &binding)?;
+            let value = inner.transparent_location(quote! { (*#binding) });
+            return Ok(quote! { Self::#variant_ident #pattern => #value });
+        }
+
         let location = find_location(&self.fields, self.ident.span())?;
 
         // Build a pattern that binds the location field to a local ident
@@ -169,23 +664,52 @@ impl Variant<'_> {
         let pattern = self.make_pattern(&location, &binding)?;
 
         // Compute the value expression
-        let value = if location.is_located_error() {
-            quote! { ::pseudo_backtrace::StackError::location(#binding) }
-        } else {
-            quote! { #binding }
-        };
+        let value = location.location_expr(quote! { #binding });
 
-        Ok(quote! { #variant_ident #pattern => #value })
+        Ok(quote! { Self::#variant_ident #pattern => #value })
     }
 
     fn next_body(&self) -> Result<proc_macro2::TokenStream> {
+        if let Some(inner) = self.transparent_field() {
+            return self.transparent_arm(inner, |inner, value| inner.transparent_next(value));
+        }
+        self.source_arm(|source, value| source.next_expr(value))
+    }
+
+    fn error_source_body(&self) -> Result<proc_macro2::TokenStream> {
+        if let Some(inner) = self.transparent_field() {
+            return self.transparent_arm(inner, |inner, value| inner.transparent_source(value));
+        }
+        self.source_arm(|source, value| source.source_expr(value))
+    }
+
+    /// Builds a match arm that binds the `transparent` inner field and passes it to `expr`.
+    fn transparent_arm(
+        &self,
+        inner: &Field<'_>,
+        expr: impl Fn(&Field<'_>, proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    ) -> Result<proc_macro2::TokenStream> {
+        let variant_ident = self.ident.clone();
+        let binding = quote::format_ident!("__stack_error_inner");
+        let pattern = self.make_pattern(inner, &binding)?;
+        let body = expr(inner, quote! { (*#binding) });
+        Ok(quote! { Self::#variant_ident #pattern => { #body } })
+    }
+
+    /// Builds a match arm that binds the source field and passes it to `expr`, or yields `None`.
+    fn source_arm(
+        &self,
+        expr: impl Fn(&Field<'_>, proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    ) -> Result<proc_macro2::TokenStream> {
         let variant_ident = self.ident.clone();
         let Some(source) = find_source(&self.fields)? else {
             let ts = match self.kind() {
-                crate::ast::ContainerKind::Struct => quote! { #variant_ident { .. } => { None } },
+                crate::ast::ContainerKind::Struct => {
+                    quote! { Self::#variant_ident { .. } => { None } }
+                }
                 crate::ast::ContainerKind::Tuple => {
                     let elems = self.fields.iter().map(|_| quote! {_});
-                    quote! { #variant_ident( #(#elems),* ) => { None } }
+                    quote! { Self::#variant_ident( #(#elems),* ) => { None } }
                 }
             };
             return Ok(ts);
@@ -195,46 +719,400 @@ impl Variant<'_> {
         let binding = quote::format_ident!("__stack_error_source");
         let pattern = self.make_pattern(&source, &binding)?;
 
-        // Build the body depending on Option<T> and stack_error kind
-        let kind = source.stack_error_kind();
-        let is_option = option_inner_type(&source.ty).is_some();
+        // Build the body depending on Option<T> and stack_error kind, from the field as a place
+        // like `self.field` in a struct
+        let body = expr(&source, quote! { (*#binding) });
 
-        let body = match (is_option, kind) {
-            (true, StackErrorKind::Stacked) => {
-                quote! {
-                    #binding
-                        .as_ref()
-                        .map(|__s| ::pseudo_backtrace::Chain::Stacked(__s.as_dyn_stack_error()))
+        Ok(quote! { Self::#variant_ident #pattern => { #body } })
+    }
+}
+
+impl Enum<'_> {
+    fn display_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let has_display = self.attrs.display.is_some()
+            || self.variants.iter().any(|v| v.attrs.display.is_some())
+            || (self.attrs.impl_error.is_some()
+                && self
+                    .variants
+                    .iter()
+                    .all(|v| v.transparent_field().is_some()));
+        if !has_display {
+            return Ok(None);
+        }
+
+        let mut arms = Vec::with_capacity(self.variants.len());
+        let mut uses = Vec::new();
+        for variant in &self.variants {
+            let variant_ident = &variant.ident;
+            if let (Some(inner), None) = (variant.transparent_field(), &variant.attrs.display) {
+                let member = &inner.member;
+                arms.push(quote! {
+                    Self::#variant_ident { #member: __inner } => ::core::fmt::Display::fmt(__inner, __formatter)
+                });
+                uses.push((inner.ty.clone(), FmtTrait::Display));
+                continue;
+            }
+
+            let Some(display) = variant
+                .attrs
+                .display
+                .as_ref()
+                .or(self.attrs.display.as_ref())
+            else {
+                return Err(Error::new_spanned(
+                    variant.original,
+                    "missing `#[stack_error(display = \"...\")]` attribute on variant",
+                ));
+            };
+            let formatted = display.expand(&variant.fields)?;
+            let pattern = formatted.pattern();
+            let fmt = &formatted.fmt;
+            arms.push(quote! {
+                Self::#variant_ident #pattern => ::core::write!(__formatter, #fmt)
+            });
+            uses.extend(formatted.uses);
+        }
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let where_clause = fmt_where_clause(self.generics, where_clause, &uses);
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#arms,)*
+                    }
                 }
             }
-            (true, StackErrorKind::Std) => {
-                quote! {
-                    #binding
-                        .as_ref()
-                        .map(|__s| ::pseudo_backtrace::Chain::Std(__s.as_dyn_std_error()))
+        }))
+    }
+}
+
+impl Enum<'_> {
+    fn debug_impl(&self) -> Option<proc_macro2::TokenStream> {
+        self.attrs.debug.as_ref()?;
+
+        let krate = &self.krate;
+        let arms = self.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let (pattern, body) = structural_debug(variant_ident, &variant.fields);
+            quote! { Self::#variant_ident #pattern => { #body } }
+        });
+        let fields = self
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().cloned())
+            .collect::<Vec<_>>();
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let mut tracker = BoundsTracker::new(self.generics, &self.krate);
+        tracker.collect_enum(self);
+        let where_clause = debug_where_clause(
+            self.generics,
+            where_clause,
+            &fields,
+            &tracker,
+            self.attrs.impl_error.is_some(),
+        );
+
+        Some(quote! {
+            impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    if __formatter.alternate() {
+                        match self {
+                            #(#arms,)*
+                        }
+                    } else {
+                        #krate::private::debug_chain(self, __formatter)
+                    }
                 }
             }
-            (false, StackErrorKind::Stacked) => {
-                quote! {
-                    ::core::option::Option::Some(::pseudo_backtrace::Chain::Stacked(
-                        #binding.as_dyn_stack_error(),
-                    ))
+        })
+    }
+
+    fn partial_eq_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        if self.attrs.partial_eq.is_none() && self.attrs.eq.is_none() {
+            return Ok(None);
+        }
+
+        let mut all_compared = Vec::new();
+        let mut arms = Vec::with_capacity(self.variants.len());
+        for variant in &self.variants {
+            let compared = eq_fields(
+                &variant.fields,
+                variant.transparent_field().is_some(),
+                variant.ident.span(),
+            )?;
+            let self_pattern = eq_pattern(variant, &compared, "__self");
+            let other_pattern = eq_pattern(variant, &compared, "__other");
+            let comparisons = compared.iter().map(|(f, is_source)| {
+                let (a, b) = eq_bindings(&variant.fields, f);
+                f.eq_expr(*is_source, quote! { #a }, quote! { #b })
+            });
+            let variant_ident = &variant.ident;
+            arms.push(quote! {
+                (Self::#variant_ident #self_pattern, Self::#variant_ident #other_pattern) => {
+                    true #(&& #comparisons)*
+                }
+            });
+            all_compared.extend(compared);
+        }
+        if self.variants.len() > 1 {
+            arms.push(quote! { _ => false });
+        }
+
+        let ident = &self.ident;
+        let impl_eq = eq_impl(ident, self.generics, self.attrs.eq.is_some(), &all_compared);
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let where_clause = eq_where_clause(self.generics, where_clause, &all_compared, false);
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::cmp::PartialEq for #ident #ty_generics #where_clause {
+                fn eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        #(#arms,)*
+                    }
                 }
             }
-            (false, StackErrorKind::Std) => {
-                quote! {
-                    ::core::option::Option::Some(::pseudo_backtrace::Chain::Std(
-                        #binding.as_dyn_std_error(),
-                    ))
+
+            #impl_eq
+        }))
+    }
+
+    fn error_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        if self.attrs.impl_error.is_none() {
+            return Ok(None);
+        }
+
+        let arms = self
+            .variants
+            .iter()
+            .map(|v| v.error_source_body())
+            .collect::<Result<Vec<_>>>()?;
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let mut tracker = BoundsTracker::new(self.generics, &self.krate);
+        tracker.collect_enum(self);
+        let where_clause = tracker.make_error_where_clause(where_clause);
+
+        Ok(Some(quote! {
+            impl #impl_generics ::core::error::Error for #ident #ty_generics #where_clause {
+                fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                    match self {
+                        #(#arms,)*
+                    }
                 }
             }
+        }))
+    }
+}
+
+impl Enum<'_> {
+    fn conversion_impls(&self) -> Result<Vec<proc_macro2::TokenStream>> {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let mut impls = Vec::new();
+        for variant in &self.variants {
+            let wants_from = variant.attrs.from.is_some()
+                || variant.fields.iter().any(|f| f.attrs.from.is_some());
+            if !wants_from {
+                continue;
+            }
+
+            let variant_ident = &variant.ident;
+            let (source_ty, body) = from_body(
+                quote! { Self::#variant_ident },
+                &variant.fields,
+                variant.transparent_field().is_some(),
+                variant.ident.span(),
+            )?;
+            impls.push(quote! {
+                impl #impl_generics ::core::convert::From<#source_ty> for #ident #ty_generics #where_clause {
+                    #[track_caller]
+                    fn from(source: #source_ty) -> Self {
+                        #body
+                    }
+                }
+            });
+        }
+
+        Ok(impls)
+    }
+
+    fn size_assertion(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let Some(max_size) = &self.attrs.max_size else {
+            return Ok(None);
+        };
+        // a tuple of the field types approximates the variant payload
+        let parts = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let tys = variant.fields.iter().map(|f| f.ty.clone()).collect();
+                (syn::ext::IdentExt::unraw(&variant.ident).to_string(), tys)
+            })
+            .collect::<Vec<_>>();
+        size_assertion(&self.ident, self.generics, max_size, "variant", &parts).map(Some)
+    }
+
+    fn boxed_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        let Some(boxed) = &self.attrs.boxed else {
+            return Ok(None);
         };
 
-        Ok(quote! { #variant_ident #pattern => { #body } })
+        let mut from_types = Vec::new();
+        for variant in &self.variants {
+            let wants_from = variant.attrs.from.is_some()
+                || variant.fields.iter().any(|f| f.attrs.from.is_some());
+            if wants_from {
+                let (source_ty, _) = from_body(
+                    quote! { Self },
+                    &variant.fields,
+                    variant.transparent_field().is_some(),
+                    variant.ident.span(),
+                )?;
+                from_types.push(source_ty);
+            }
+        }
+
+        let has_kind = self.attrs.kind_enum.is_some();
+        boxed_wrapper(
+            boxed,
+            self.vis,
+            &self.ident,
+            self.generics,
+            &from_types,
+            has_kind,
+            &self.krate,
+        )
+        .map(Some)
+    }
+}
+
+impl Enum<'_> {
+    fn kind_impl(&self) -> Option<proc_macro2::TokenStream> {
+        self.attrs.kind_enum.as_ref()?;
+
+        let ident = &self.ident;
+        let vis = self.vis;
+        let kind_ident = quote::format_ident!("{}Kind", ident);
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let doc = format!("[`{}::{}`]", ident, variant_ident);
+                quote! {
+                    #[doc = #doc]
+                    #variant_ident
+                }
+            })
+            .collect::<Vec<_>>();
+        let kind_arms = self.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            quote! { Self::#variant_ident { .. } => #kind_ident::#variant_ident }
+        });
+        let name_arms = self.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let name = syn::ext::IdentExt::unraw(variant_ident).to_string();
+            quote! { Self::#variant_ident => #name }
+        });
+
+        let enum_doc = format!("The variants of [`{}`], without their fields.", ident);
+        let fn_doc = format!("Returns which variant of [`{}`] this is.", ident);
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        Some(quote! {
+            #[doc = #enum_doc]
+            #[derive(
+                ::core::fmt::Debug,
+                ::core::clone::Clone,
+                ::core::marker::Copy,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::core::hash::Hash,
+            )]
+            #vis enum #kind_ident {
+                #(#variants,)*
+            }
+
+            impl #kind_ident {
+                /// Returns the name of the variant.
+                #vis const fn name(self) -> &'static str {
+                    match self {
+                        #(#name_arms,)*
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for #kind_ident {
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    __formatter.write_str(self.name())
+                }
+            }
+
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #[doc = #fn_doc]
+                #vis const fn kind(&self) -> #kind_ident {
+                    match self {
+                        #(#kind_arms,)*
+                    }
+                }
+            }
+        })
+    }
+
+    fn constructors_impl(&self) -> Result<Option<proc_macro2::TokenStream>> {
+        if self.attrs.constructors.is_none() {
+            return Ok(None);
+        }
+
+        let ident = &self.ident;
+        let fns = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let doc = format!(
+                    "Creates a new [`{}::{}`], recording the caller as its location.",
+                    ident, variant_ident
+                );
+                constructor(
+                    self.vis,
+                    &to_snake_case(variant_ident)?,
+                    quote! { Self::#variant_ident },
+                    &variant.fields,
+                    variant.transparent_field().is_some(),
+                    variant.ident.span(),
+                    &doc,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        Ok(Some(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#fns)*
+            }
+        }))
     }
 }
 
 fn expand_enum(input: Enum<'_>) -> Result<proc_macro2::TokenStream> {
+    let display_impl = input.display_impl()?;
+    let debug_impl = input.debug_impl();
+    let error_impl = input.error_impl()?;
+    let from_impls = input.conversion_impls()?;
+    let kind_impl = input.kind_impl();
+    let boxed_impl = input.boxed_impl()?;
+    let size_assertion = input.size_assertion()?;
+    let partial_eq_impl = input.partial_eq_impl()?;
+    let constructors_impl = input.constructors_impl()?;
+
     let location_arms = input
         .variants
         .iter()
@@ -248,19 +1126,16 @@ fn expand_enum(input: Enum<'_>) -> Result<proc_macro2::TokenStream> {
         .collect::<Result<Vec<_>>>()?;
 
     let ident = input.ident.clone();
+    let krate = &input.krate;
     let location_fn = quote! {
         fn location(&self) -> &'static ::core::panic::Location<'static> {
-            use #ident::*;
             match self {
                 #(#location_arms,)*
             }
         }
     };
     let next_fn = quote! {
-        fn next<'pseudo_backtrace>(&'pseudo_backtrace self) -> ::core::option::Option<::pseudo_backtrace::Chain<'pseudo_backtrace>> {
-            use #ident::*;
-            use ::pseudo_backtrace::private::AsDynStdError as _;
-            use ::pseudo_backtrace::private::AsDynStackError as _;
+        fn next<'pseudo_backtrace>(&'pseudo_backtrace self) -> ::core::option::Option<#krate::Chain<'pseudo_backtrace>> {
             match self {
                 #(#next_arms,)*
             }
@@ -268,19 +1143,524 @@ fn expand_enum(input: Enum<'_>) -> Result<proc_macro2::TokenStream> {
     };
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let mut tracker = BoundsTracker::new(input.generics);
+    let mut tracker = BoundsTracker::new(input.generics, &input.krate);
     tracker.collect_enum(&input);
-    let where_clause = tracker.make_where_clause(where_clause);
+    // `StackError: Error`, so the impl must be at least as strict as a generated `Error` impl
+    let where_clause = if input.attrs.impl_error.is_some() {
+        tracker.make_error_where_clause(where_clause)
+    } else {
+        tracker.make_where_clause(where_clause)
+    };
 
     Ok(quote! {
-        impl #impl_generics ::pseudo_backtrace::StackError for #ident #ty_generics #where_clause {
+        impl #impl_generics #krate::StackError for #ident #ty_generics #where_clause {
            #location_fn
            #next_fn
         }
+
+        #display_impl
+        #debug_impl
+        #error_impl
+        #partial_eq_impl
+        #(#from_impls)*
+        #constructors_impl
+        #kind_impl
+        #boxed_impl
+        #size_assertion
+    })
+}
+
+/// Counts the `Box`, `Arc`, `Rc` and reference layers that [peel_wrappers] strips from `ty`.
+fn deref_depth(ty: &syn::Type) -> usize {
+    match ty {
+        syn::Type::Reference(r) => 1 + deref_depth(&r.elem),
+        syn::Type::Paren(ty) => deref_depth(&ty.elem),
+        syn::Type::Group(ty) => deref_depth(&ty.elem),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let Some(last) = path.path.segments.last() else {
+                return 0;
+            };
+            match single_type_arg(last) {
+                Some(inner) if matches!(last.ident.to_string().as_str(), "Box" | "Arc" | "Rc") => {
+                    1 + deref_depth(inner)
+                }
+                Some(inner) if last.ident == "Option" => deref_depth(inner),
+                _ => 0,
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Gives every token of `path` the given span, so that errors on it point at user code.
+fn respan(path: &syn::Path, span: Span) -> proc_macro2::TokenStream {
+    use quote::ToTokens;
+
+    path.to_token_stream()
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+/// Builds a `const` check that `ident` fits in `max_size` bytes.
+///
+/// `parts` are the fields or variants with their types. When the check fails, the largest part
+/// is named in the error, since a `const` panic cannot format the sizes.
+fn size_assertion(
+    ident: &Ident,
+    generics: &syn::Generics,
+    max_size: &MaxSize,
+    part: &str,
+    parts: &[(String, Vec<syn::Type>)],
+) -> Result<proc_macro2::TokenStream> {
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            max_size.lit.span(),
+            "`max_size` is not supported on generic types",
+        ));
+    }
+
+    let bytes = max_size.bytes;
+    let len = parts.len();
+    let sizes = parts.iter().map(|(_, tys)| {
+        quote! { ::core::mem::size_of::<( #(#tys,)* )>() }
+    });
+    let arms = parts.iter().enumerate().map(|(i, (name, _))| {
+        let message = format!(
+            "`{}` is larger than `max_size = {}` bytes. its largest {} is `{}`",
+            ident, bytes, part, name
+        );
+        quote! { #i => ::core::panic!(#message) }
+    });
+    let message = format!("`{}` is larger than `max_size = {}` bytes", ident, bytes);
+
+    Ok(quote! {
+        const _: () = {
+            if ::core::mem::size_of::<#ident>() > #bytes {
+                let sizes: [usize; #len] = [#(#sizes),*];
+                let mut largest = 0;
+                let mut i = 1;
+                while i < #len {
+                    if sizes[i] > sizes[largest] {
+                        largest = i;
+                    }
+                    i += 1;
+                }
+                match largest {
+                    #(#arms,)*
+                    _ => ::core::panic!(#message),
+                }
+            }
+        };
+    })
+}
+
+fn member_name(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => syn::ext::IdentExt::unraw(ident).to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// Returns the fields `partial_eq` compares, each paired with whether it is the source.
+///
+/// The location field is left out, unless it delegates to a source that is compared anyway.
+fn eq_fields<'a>(
+    fields: &[Field<'a>],
+    transparent: bool,
+    span: Span,
+) -> Result<Vec<(Field<'a>, bool)>> {
+    if transparent {
+        return Ok(vec![(fields[0].clone(), true)]);
+    }
+
+    let location = find_location(fields, span)?;
+    let source = find_source(fields)?;
+    Ok(fields
+        .iter()
+        .filter(|f| location.is_location_delegate() || f.member != location.member)
+        .map(|f| {
+            let is_source = source.as_ref().is_some_and(|s| s.member == f.member);
+            (f.clone(), is_source)
+        })
+        .collect())
+}
+
+/// Names the bindings of `field` in the patterns built by [eq_pattern].
+fn eq_bindings(fields: &[Field<'_>], field: &Field<'_>) -> (Ident, Ident) {
+    let i = fields
+        .iter()
+        .position(|f| f.member == field.member)
+        .unwrap_or_default();
+    (
+        quote::format_ident!("__self{}", i),
+        quote::format_ident!("__other{}", i),
+    )
+}
+
+/// Builds a variant pattern binding the compared fields as `{prefix}{index}`.
+fn eq_pattern(
+    variant: &Variant<'_>,
+    compared: &[(Field<'_>, bool)],
+    prefix: &str,
+) -> proc_macro2::TokenStream {
+    let bindings = variant.fields.iter().enumerate().map(|(i, f)| {
+        if compared.iter().any(|(c, _)| c.member == f.member) {
+            let binding = quote::format_ident!("{}{}", prefix, i);
+            quote! { #binding }
+        } else {
+            quote! { _ }
+        }
+    });
+    match variant.kind() {
+        crate::ast::ContainerKind::Struct => {
+            let members = variant.fields.iter().map(|f| &f.member);
+            quote! { { #(#members: #bindings),* } }
+        }
+        crate::ast::ContainerKind::Tuple => quote! { ( #(#bindings),* ) },
+    }
+}
+
+/// Adds the bounds `partial_eq` needs for every compared field whose type mentions a generic parameter.
+///
+/// With `total`, the bound is `Eq` and is added for concrete types too, so that a field that is
+/// not `Eq` is reported on its type.
+fn eq_where_clause(
+    generics: &syn::Generics,
+    where_clause: Option<&syn::WhereClause>,
+    compared: &[(Field<'_>, bool)],
+    total: bool,
+) -> syn::WhereClause {
+    let params = generics.type_params().map(|p| p.ident.clone()).collect();
+    let mut where_clause = where_clause.cloned().unwrap_or(syn::WhereClause {
+        where_token: syn::Token![where](Span::call_site()),
+        predicates: syn::punctuated::Punctuated::new(),
+    });
+
+    for (f, is_source) in compared {
+        let ty = &f.ty;
+        let is_generic = mentions_param(ty, &params);
+        if f.eq_by_value(*is_source) {
+            if total {
+                let span = syn::spanned::Spanned::span(ty);
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote_spanned!(span=> #ty: ::core::cmp::Eq));
+            } else if is_generic {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: ::core::cmp::PartialEq));
+            }
+        } else if is_generic && f.stack_error_kind() == StackErrorKind::Std {
+            let ty = peel_wrappers(ty);
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: ::core::error::Error));
+        }
+    }
+
+    where_clause
+}
+
+/// Builds the `Eq` impl for `eq`.
+fn eq_impl(
+    ident: &Ident,
+    generics: &syn::Generics,
+    eq: bool,
+    compared: &[(Field<'_>, bool)],
+) -> Option<proc_macro2::TokenStream> {
+    if !eq {
+        return None;
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let where_clause = eq_where_clause(generics, where_clause, compared, true);
+    Some(quote! {
+        impl #impl_generics ::core::cmp::Eq for #ident #ty_generics #where_clause {}
+    })
+}
+
+/// Builds the `boxed` wrapper: a one-pointer struct that forwards everything to the boxed type.
+fn boxed_wrapper(
+    boxed: &Boxed<'_>,
+    vis: &syn::Visibility,
+    ident: &Ident,
+    generics: &syn::Generics,
+    from_types: &[syn::Type],
+    has_kind: bool,
+    krate: &syn::Path,
+) -> Result<proc_macro2::TokenStream> {
+    let name = match &boxed.name {
+        Some(name) => name.clone(),
+        None => {
+            let stripped = ident
+                .to_string()
+                .strip_suffix("Inner")
+                .filter(|name| !name.is_empty())
+                .map(|name| Ident::new(name, ident.span()));
+            stripped.ok_or_else(|| {
+                Error::new(
+                    boxed.flag.span,
+                    format!(
+                        "`boxed` needs a type named like `{}Inner`, or a name such as `boxed = Error`",
+                        ident
+                    ),
+                )
+            })?
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let bounded = |bound: proc_macro2::TokenStream| {
+        let mut where_clause = where_clause.cloned().unwrap_or(syn::WhereClause {
+            where_token: syn::Token![where](Span::call_site()),
+            predicates: syn::punctuated::Punctuated::new(),
+        });
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#ident #ty_generics: #bound));
+        where_clause
+    };
+    let stack_where = bounded(quote! { #krate::StackError });
+    let error_where = bounded(quote! { ::core::error::Error });
+    let display_where = bounded(quote! { ::core::fmt::Display });
+    let debug_where = bounded(quote! { ::core::fmt::Debug });
+
+    let struct_doc = format!(
+        "A boxed [`{}`], keeping `Result`s that carry it one pointer wide.",
+        ident
+    );
+    let kind_fn = has_kind.then(|| {
+        let kind_ident = quote::format_ident!("{}Kind", ident);
+        let doc = format!("Returns which variant of [`{}`] this is.", ident);
+        quote! {
+            #[doc = #doc]
+            #vis fn kind(&self) -> #kind_ident {
+                self.0.kind()
+            }
+        }
+    });
+    let from_impls = from_types.iter().map(|ty| {
+        let from_where = bounded(quote! { ::core::convert::From<#ty> });
+        quote! {
+            impl #impl_generics ::core::convert::From<#ty> for #name #ty_generics #from_where {
+                #[track_caller]
+                fn from(source: #ty) -> Self {
+                    Self(#krate::private::Box::new(
+                        <#ident #ty_generics as ::core::convert::From<#ty>>::from(source),
+                    ))
+                }
+            }
+        }
+    });
+
+    let wrapper = quote! {
+        #[doc = #struct_doc]
+        #vis struct #name #generics (#krate::private::Box<#ident #ty_generics>) #where_clause;
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the boxed error.
+            #vis fn inner(&self) -> &#ident #ty_generics {
+                &self.0
+            }
+
+            /// Unboxes the error.
+            #vis fn into_inner(self) -> #ident #ty_generics {
+                *self.0
+            }
+
+            #kind_fn
+        }
+
+        impl #impl_generics #krate::StackError for #name #ty_generics #stack_where {
+            fn location(&self) -> &'static ::core::panic::Location<'static> {
+                #krate::StackError::location(&*self.0)
+            }
+
+            fn next<'pseudo_backtrace>(&'pseudo_backtrace self) -> ::core::option::Option<#krate::Chain<'pseudo_backtrace>> {
+                #krate::StackError::next(&*self.0)
+            }
+        }
+
+        impl #impl_generics ::core::error::Error for #name #ty_generics #error_where {
+            fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                ::core::error::Error::source(&*self.0)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #display_where {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&*self.0, __formatter)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #debug_where {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&*self.0, __formatter)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for #name #ty_generics #where_clause {
+            fn from(inner: #ident #ty_generics) -> Self {
+                Self(#krate::private::Box::new(inner))
+            }
+        }
+
+        #(#from_impls)*
+    };
+
+    // `Box` only exists with the `alloc` feature, which the macro checks at the attribute
+    let spanned_krate = respan(krate, boxed.flag.span);
+    Ok(quote_spanned! {boxed.flag.span=>
+        #spanned_krate::private::boxed! {
+            #wrapper
+        }
+    })
+}
+
+/// Builds the body of a `#[track_caller]` `From` impl that moves the source into `ctor`.
+///
+/// Returns the source type together with the body. The location is filled from
+/// `Location::caller()` and every other field from `Default::default()`.
+fn from_body(
+    ctor: proc_macro2::TokenStream,
+    fields: &[Field<'_>],
+    transparent: bool,
+    source_span: Span,
+) -> Result<(syn::Type, proc_macro2::TokenStream)> {
+    if transparent {
+        let inner = &fields[0];
+        let member = &inner.member;
+        return Ok((inner.ty.clone(), quote! { #ctor { #member: source } }));
+    }
+
+    let Some(source) = find_source(fields)? else {
+        return Err(Error::new(
+            source_span,
+            "`from` needs a source field. mark it with `#[from]` or `#[source]`",
+        ));
+    };
+    let location = find_location(fields, source_span)?;
+
+    let inits = fields
+        .iter()
+        .map(|f| {
+            let member = &f.member;
+            if f.member == source.member {
+                Ok(quote! { #member: source })
+            } else if f.member == location.member {
+                let value = location.caller_location("from")?;
+                Ok(quote! { #member: #value })
+            } else {
+                Ok(quote! { #member: ::core::default::Default::default() })
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((source.ty.clone(), quote! { #ctor { #(#inits,)* } }))
+}
+
+/// Builds a `#[track_caller]` constructor taking every field except the location.
+fn constructor(
+    vis: &syn::Visibility,
+    name: &Ident,
+    ctor: proc_macro2::TokenStream,
+    fields: &[Field<'_>],
+    transparent: bool,
+    source_span: Span,
+    doc: &str,
+) -> Result<proc_macro2::TokenStream> {
+    // a `transparent` inner error and a delegated location field carry their own location
+    let location = if transparent {
+        None
+    } else {
+        let location = find_location(fields, source_span)?;
+        let location_is_source =
+            find_source(fields)?.is_some_and(|source| source.member == location.member);
+        (!location_is_source && !location.is_location_delegate()).then_some(location)
+    };
+
+    let mut args = Vec::new();
+    let mut inits = Vec::new();
+    for f in fields {
+        let member = &f.member;
+        if let Some(location) = location.as_ref().filter(|l| l.member == f.member) {
+            let value = location.caller_location("constructors")?;
+            inits.push(quote! { #member: #value });
+            continue;
+        }
+
+        let arg = match &f.member {
+            syn::Member::Named(ident) => ident.clone(),
+            syn::Member::Unnamed(index) => quote::format_ident!("arg{}", index.index),
+        };
+        let ty = &f.ty;
+        args.push(quote! { #arg: #ty });
+        inits.push(quote! { #member: #arg });
+    }
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[track_caller]
+        #vis fn #name(#(#args),*) -> Self {
+            #ctor { #(#inits,)* }
+        }
     })
 }
 
+/// Converts a variant name such as `NotFound` or `IOError` into `not_found` or `io_error`.
+fn to_snake_case(ident: &Ident) -> Result<Ident> {
+    let name = syn::ext::IdentExt::unraw(ident).to_string();
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                || (prev.is_some_and(|p| p.is_uppercase())
+                    && next.is_some_and(|n| n.is_lowercase()));
+            if starts_word && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    if syn::parse_str::<Ident>(&snake).is_ok() {
+        Ok(Ident::new(&snake, ident.span()))
+    } else if matches!(snake.as_str(), "crate" | "self" | "super") {
+        // path keywords cannot be raw identifiers
+        Err(Error::new(
+            ident.span(),
+            format!(
+                "cannot generate a constructor named `{}`. rename the variant or remove `constructors`",
+                snake
+            ),
+        ))
+    } else {
+        // keywords such as `type` or `match`
+        Ok(Ident::new_raw(&snake, ident.span()))
+    }
+}
+
 fn find_location<'a>(fields: &[Field<'a>], source_span: Span) -> Result<Field<'a>> {
+    let location = find_location_field(fields, source_span)?;
+    if !location.is_location_delegate() && is_location_by_value(&location.ty) {
+        return Err(Error::new_spanned(
+            &location.ty,
+            "a `Location` stored by value cannot be returned from `StackError::location`. \
+             store `&'static Location<'static>` or `Option<&'static Location<'static>>` instead",
+        ));
+    }
+    Ok(location)
+}
+
+fn find_location_field<'a>(fields: &[Field<'a>], source_span: Span) -> Result<Field<'a>> {
     // find #[loaction] attribute
     let mut it = fields.iter().filter(|f| f.attrs.location.is_some());
     match (it.next(), it.next()) {
@@ -297,10 +1677,13 @@ fn find_location<'a>(fields: &[Field<'a>], source_span: Span) -> Result<Field<'a
     }
 
     // find named `location`
-    let mut it = fields.iter().filter(|f| match &f.member {
-        syn::Member::Named(ident) => ident == "location",
-        _ => false,
-    });
+    let mut it = fields
+        .iter()
+        .filter(|f| f.attrs.skip.is_none())
+        .filter(|f| match &f.member {
+            syn::Member::Named(ident) => ident == "location",
+            _ => false,
+        });
     if let Some(f) = it.next() {
         return Ok(f.clone());
     };
@@ -317,10 +1700,10 @@ fn find_location<'a>(fields: &[Field<'a>], source_span: Span) -> Result<Field<'a
 }
 
 fn find_source<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
-    // find #[source] and #[stack_error] attribute
-    let mut it = fields
-        .iter()
-        .filter(|f| f.attrs.source.is_some() || f.attrs.stack_error.is_some());
+    // find #[source], #[from] and #[stack_error] attribute
+    let mut it = fields.iter().filter(|f| {
+        f.attrs.source.is_some() || f.attrs.stack_error.is_some() || f.attrs.from.is_some()
+    });
     match (it.next(), it.next()) {
         (Some(_), Some(second)) => {
             return Err(Error::new_spanned(
@@ -335,10 +1718,13 @@ fn find_source<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
     }
 
     // find named `source`
-    let mut it = fields.iter().filter(|f| match &f.member {
-        syn::Member::Named(ident) => ident == "source",
-        _ => false,
-    });
+    let mut it = fields
+        .iter()
+        .filter(|f| f.attrs.skip.is_none())
+        .filter(|f| match &f.member {
+            syn::Member::Named(ident) => ident == "source",
+            _ => false,
+        });
     if let Some(f) = it.next() {
         return Ok(Some(f.clone()));
     };
@@ -352,11 +1738,13 @@ fn find_source<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
 }
 
 fn find_located_error<'a>(fields: &[Field<'a>]) -> Result<Option<Field<'a>>> {
-    let mut it = fields.iter().filter(|f| is_located_error(&f.ty));
+    let mut it = fields
+        .iter()
+        .filter(|f| f.attrs.skip.is_none() && is_located_error(&f.ty));
     match (it.next(), it.next()) {
         (Some(_), Some(second)) => Err(Error::new_spanned(
             second.original,
-            "duplicate `LocatedError` field",
+            "duplicate `LocatedError` field. mark the ones that are not the source `#[stack_error(skip)]`",
         )),
         (Some(first), None) => Ok(Some(first.clone())),
         _ => Ok(None),
@@ -378,6 +1766,85 @@ fn is_located_error(ty: &syn::Type) -> bool {
     last.ident == "LocatedError"
 }
 
+/// Infers the kind of a non-generic source field without a `#[stack_error(..)]` attribute.
+///
+/// `Option`, `Box`, `Arc`, `Rc` and references are looked through. `dyn Error` objects are
+/// `Std`, `dyn StackError` objects are `Stacked`, and any named type is left to be resolved by
+/// the compiler, which can tell whether it implements `StackError`.
+fn infer_source_kind(ty: &syn::Type) -> StackErrorKind {
+    match peel_wrappers(ty) {
+        syn::Type::TraitObject(obj) => {
+            let mut kind = StackErrorKind::Auto;
+            for bound in &obj.bounds {
+                let syn::TypeParamBound::Trait(bound) = bound else {
+                    continue;
+                };
+                let Some(last) = bound.path.segments.last() else {
+                    continue;
+                };
+                if last.ident == "StackError" {
+                    return StackErrorKind::Stacked;
+                }
+                if last.ident == "Error" {
+                    kind = StackErrorKind::Std;
+                }
+            }
+            kind
+        }
+        _ => StackErrorKind::Auto,
+    }
+}
+
+/// Strips `Option`, `Box`, `Arc`, `Rc` and references down to the error type they hold.
+fn peel_wrappers(ty: &syn::Type) -> &syn::Type {
+    match ty {
+        syn::Type::Reference(r) => peel_wrappers(&r.elem),
+        syn::Type::Paren(ty) => peel_wrappers(&ty.elem),
+        syn::Type::Group(ty) => peel_wrappers(&ty.elem),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let Some(last) = path.path.segments.last() else {
+                return ty;
+            };
+            match single_type_arg(last) {
+                Some(inner)
+                    if matches!(
+                        last.ident.to_string().as_str(),
+                        "Option" | "Box" | "Arc" | "Rc"
+                    ) =>
+                {
+                    peel_wrappers(inner)
+                }
+                _ => ty,
+            }
+        }
+        _ => ty,
+    }
+}
+
+fn single_type_arg(segment: &syn::PathSegment) -> Option<&syn::Type> {
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Returns true for `Location<'_>` (possibly path-qualified), which `location()` cannot borrow for `'static`.
+fn is_location_by_value(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|last| last.ident == "Location"),
+        syn::Type::Paren(ty) => is_location_by_value(&ty.elem),
+        syn::Type::Group(ty) => is_location_by_value(&ty.elem),
+        _ => false,
+    }
+}
+
 fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
     let path = match ty {
         syn::Type::Path(ty) => &ty.path,
@@ -405,31 +1872,71 @@ fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
 }
 
 pub struct BoundsTracker {
+    krate: syn::Path,
     params: std::collections::BTreeSet<syn::Ident>,
     stack_bounds: std::collections::BTreeMap<String, Vec<StackErrorKind>>,
+    location_bounds: std::collections::BTreeSet<String>,
 }
 
 impl BoundsTracker {
-    pub fn new(generics: &syn::Generics) -> Self {
+    pub fn new(generics: &syn::Generics, krate: &syn::Path) -> Self {
         let params = generics.type_params().map(|p| p.ident.clone()).collect();
         Self {
+            krate: krate.clone(),
             params,
             stack_bounds: Default::default(),
+            location_bounds: Default::default(),
+        }
+    }
+
+    pub fn collect_struct(&mut self, st: &Struct<'_>) {
+        match st.transparent_field() {
+            Some(inner) => self.collect_field(inner),
+            None => self.collect(&st.fields),
         }
     }
 
     pub fn collect(&mut self, fields: &[Field<'_>]) {
-        use quote::ToTokens;
+        if let Ok(location) = find_location(fields, Span::call_site()) {
+            self.collect_location(&location);
+        }
         let Ok(Some(f)) = find_source(fields) else {
             return;
         };
+        self.collect_field(&f);
+    }
+
+    fn collect_location(&mut self, f: &Field<'_>) {
+        use quote::ToTokens;
 
         let mut found = false;
         crawl(&f.ty, &self.params, &mut found);
+        if !found {
+            return;
+        }
+
+        let ty = f.ty.to_token_stream().to_string();
+        if f.attrs.location.as_ref().is_some_and(|l| l.delegate) {
+            self.stack_bounds
+                .entry(ty)
+                .or_default()
+                .push(StackErrorKind::Stacked);
+        } else {
+            self.location_bounds.insert(ty);
+        }
+    }
+
+    fn collect_field(&mut self, f: &Field<'_>) {
+        use quote::ToTokens;
+
+        // `next` and `source` reach the error through auto-deref, so bound what they reach
+        let ty = peel_wrappers(&f.ty);
+        let mut found = false;
+        crawl(ty, &self.params, &mut found);
 
         if found {
             self.stack_bounds
-                .entry(f.ty.to_token_stream().to_string())
+                .entry(ty.to_token_stream().to_string())
                 .or_default()
                 .push(f.stack_error_kind());
         }
@@ -437,7 +1944,10 @@ impl BoundsTracker {
 
     pub fn collect_enum(&mut self, enum_st: &Enum<'_>) {
         for variant in &enum_st.variants {
-            self.collect(&variant.fields);
+            match variant.transparent_field() {
+                Some(inner) => self.collect_field(inner),
+                None => self.collect(&variant.fields),
+            }
         }
     }
 
@@ -446,25 +1956,179 @@ impl BoundsTracker {
             where_token: syn::Token![where](Span::call_site()),
             predicates: syn::punctuated::Punctuated::new(),
         });
+        let krate = &self.krate;
 
         for (ty, kinds) in &self.stack_bounds {
             let ty: syn::Type = syn::parse_str(ty).unwrap();
             for kind in kinds {
                 let bound = match kind {
-                    StackErrorKind::Stacked => quote! {::pseudo_backtrace::StackError},
+                    StackErrorKind::Stacked => quote! {#krate::StackError},
                     StackErrorKind::Std => quote! {::core::error::Error},
+                    StackErrorKind::Auto => continue,
                 };
                 where_clause.predicates.push(syn::parse_quote!(#ty: #bound));
             }
         }
 
+        for ty in &self.location_bounds {
+            let ty: syn::Type = syn::parse_str(ty).unwrap();
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: #krate::private::AsLocation));
+        }
+
+        where_clause
+    }
+
+    /// Like [BoundsTracker::make_where_clause], for a generated `core::error::Error` impl.
+    ///
+    /// `Error::source` hands out `'static` trait objects, so generic sources must be `'static`.
+    pub fn make_error_where_clause(
+        &self,
+        where_clause: Option<&syn::WhereClause>,
+    ) -> syn::WhereClause {
+        let mut where_clause = self.make_static_where_clause(where_clause);
+        if !self.params.is_empty() {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(Self: ::core::fmt::Debug + ::core::fmt::Display));
+        }
+        where_clause
+    }
+
+    /// Bounds under which the `StackError` impl holds, for a generated `Debug` impl.
+    ///
+    /// Unlike [BoundsTracker::make_error_where_clause] this must not require `Self: Debug`,
+    /// which would make the two impls depend on each other.
+    pub fn make_debug_where_clause(
+        &self,
+        where_clause: &syn::WhereClause,
+        impl_error: bool,
+    ) -> syn::WhereClause {
+        if !impl_error {
+            return self.make_where_clause(Some(where_clause));
+        }
+        let mut where_clause = self.make_static_where_clause(Some(where_clause));
+        if !self.params.is_empty() {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(Self: ::core::fmt::Display));
+        }
+        where_clause
+    }
+
+    /// Like [BoundsTracker::make_where_clause], with generic sources required to be `'static`.
+    fn make_static_where_clause(
+        &self,
+        where_clause: Option<&syn::WhereClause>,
+    ) -> syn::WhereClause {
+        let mut where_clause = self.make_where_clause(where_clause);
+        for ty in self.stack_bounds.keys() {
+            let ty: syn::Type = syn::parse_str(ty).unwrap();
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: 'static));
+        }
         where_clause
     }
 }
 
+/// Builds a pattern binding every field and a body formatting them the way `#[derive(Debug)]` does.
+fn structural_debug(
+    name: &Ident,
+    fields: &[Field<'_>],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    use syn::ext::IdentExt;
+
+    let name = name.unraw().to_string();
+    let bindings = (0..fields.len())
+        .map(|i| quote::format_ident!("__field{}", i))
+        .collect::<Vec<_>>();
+    let is_tuple = fields
+        .iter()
+        .any(|f| matches!(f.member, syn::Member::Unnamed(_)));
+
+    if is_tuple {
+        (
+            quote! { ( #(#bindings),* ) },
+            quote! { __formatter.debug_tuple(#name) #(.field(#bindings))* .finish() },
+        )
+    } else {
+        let members = fields.iter().map(|f| &f.member);
+        let names = fields.iter().map(|f| match &f.member {
+            syn::Member::Named(ident) => ident.unraw().to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        });
+        (
+            quote! { { #(#members: #bindings),* } },
+            quote! { __formatter.debug_struct(#name) #(.field(#names, #bindings))* .finish() },
+        )
+    }
+}
+
+/// Bounds for a generated `Debug` impl: every generic field is `Debug`, and the `StackError`
+/// impl holds so that the chain can be rendered.
+fn debug_where_clause(
+    generics: &syn::Generics,
+    where_clause: Option<&syn::WhereClause>,
+    fields: &[Field<'_>],
+    tracker: &BoundsTracker,
+    impl_error: bool,
+) -> syn::WhereClause {
+    let uses = fields
+        .iter()
+        .map(|f| (f.ty.clone(), FmtTrait::Debug))
+        .collect::<Vec<_>>();
+    let where_clause = fmt_where_clause(generics, where_clause, &uses);
+    tracker.make_debug_where_clause(&where_clause, impl_error)
+}
+
+/// Adds a formatting bound for every interpolated field whose type mentions a generic parameter.
+fn fmt_where_clause(
+    generics: &syn::Generics,
+    where_clause: Option<&syn::WhereClause>,
+    uses: &[(syn::Type, FmtTrait)],
+) -> syn::WhereClause {
+    let params = generics.type_params().map(|p| p.ident.clone()).collect();
+    let mut where_clause = where_clause.cloned().unwrap_or(syn::WhereClause {
+        where_token: syn::Token![where](Span::call_site()),
+        predicates: syn::punctuated::Punctuated::new(),
+    });
+
+    for (ty, fmt_trait) in uses {
+        let mut found = false;
+        crawl(ty, &params, &mut found);
+        if found {
+            let bound = fmt_trait.to_bound();
+            where_clause.predicates.push(syn::parse_quote!(#ty: #bound));
+        }
+    }
+
+    where_clause
+}
+
+pub fn mentions_param(ty: &syn::Type, params: &std::collections::BTreeSet<syn::Ident>) -> bool {
+    let mut found = false;
+    crawl(ty, params, &mut found);
+    found
+}
+
 fn crawl(ty: &syn::Type, params: &std::collections::BTreeSet<syn::Ident>, found: &mut bool) {
-    let syn::Type::Path(ty) = ty else {
-        return;
+    let ty = match ty {
+        syn::Type::Path(ty) => ty,
+        syn::Type::Reference(ty) => return crawl(&ty.elem, params, found),
+        syn::Type::Ptr(ty) => return crawl(&ty.elem, params, found),
+        syn::Type::Paren(ty) => return crawl(&ty.elem, params, found),
+        syn::Type::Group(ty) => return crawl(&ty.elem, params, found),
+        syn::Type::Slice(ty) => return crawl(&ty.elem, params, found),
+        syn::Type::Array(ty) => return crawl(&ty.elem, params, found),
+        syn::Type::Tuple(ty) => {
+            for elem in &ty.elems {
+                crawl(elem, params, found);
+            }
+            return;
+        }
+        _ => return,
     };
 
     if let Some(qself) = &ty.qself {
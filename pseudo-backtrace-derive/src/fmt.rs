@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, Ident, Index, LitStr, Member, Result};
+
+use crate::{ast::Field, attr::Display};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FmtTrait {
+    Display,
+    Debug,
+    LowerHex,
+    UpperHex,
+    Octal,
+    Binary,
+    LowerExp,
+    UpperExp,
+}
+
+impl FmtTrait {
+    /// Reads the trait from the type at the end of a format spec such as `>8`, `#x` or `?`.
+    pub fn from_spec(spec: &str) -> Self {
+        if spec.ends_with('?') {
+            return FmtTrait::Debug;
+        }
+        match spec.chars().last() {
+            Some('x') => FmtTrait::LowerHex,
+            Some('X') => FmtTrait::UpperHex,
+            Some('o') => FmtTrait::Octal,
+            Some('b') => FmtTrait::Binary,
+            Some('e') => FmtTrait::LowerExp,
+            Some('E') => FmtTrait::UpperExp,
+            // no type: only fill, alignment, sign, width or precision
+            _ => FmtTrait::Display,
+        }
+    }
+
+    pub fn to_bound(self) -> TokenStream {
+        match self {
+            FmtTrait::Display => quote! { ::core::fmt::Display },
+            FmtTrait::Debug => quote! { ::core::fmt::Debug },
+            FmtTrait::LowerHex => quote! { ::core::fmt::LowerHex },
+            FmtTrait::UpperHex => quote! { ::core::fmt::UpperHex },
+            FmtTrait::Octal => quote! { ::core::fmt::Octal },
+            FmtTrait::Binary => quote! { ::core::fmt::Binary },
+            FmtTrait::LowerExp => quote! { ::core::fmt::LowerExp },
+            FmtTrait::UpperExp => quote! { ::core::fmt::UpperExp },
+        }
+    }
+}
+
+/// A `display` format string rewritten so that every interpolated field refers to a local binding.
+pub struct Formatted {
+    pub fmt: LitStr,
+    /// Fields referenced from the format string, paired with the binding used in the pattern.
+    pub bindings: Vec<(Member, Ident)>,
+    /// Field types paired with the formatting trait they are used through.
+    pub uses: Vec<(syn::Type, FmtTrait)>,
+}
+
+impl Formatted {
+    /// Returns `{ member: binding, .. }`, usable after `Self` or `Self::Variant`.
+    pub fn pattern(&self) -> TokenStream {
+        let bindings = self
+            .bindings
+            .iter()
+            .map(|(member, binding)| quote! { #member: #binding });
+        quote! { { #(#bindings,)* .. } }
+    }
+}
+
+impl Display<'_> {
+    pub fn expand(&self, fields: &[Field<'_>]) -> Result<Formatted> {
+        let value = self.fmt.value();
+        let span = self.fmt.span();
+
+        let mut out = String::with_capacity(value.len());
+        let mut bindings: Vec<(Member, Ident)> = Vec::new();
+        let mut uses = Vec::new();
+
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push_str("{{");
+                }
+                '{' => {
+                    let mut arg = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c == '}' || c == ':' {
+                            break;
+                        }
+                        arg.push(c);
+                        chars.next();
+                    }
+                    let mut spec = String::new();
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            break;
+                        }
+                        spec.push(c);
+                    }
+
+                    let member = if arg.is_empty() {
+                        return Err(Error::new(
+                            span,
+                            "positional `{}` is not supported in `display`. name a field such as `{0}` or `{source}`",
+                        ));
+                    } else if let Ok(index) = arg.parse::<u32>() {
+                        Member::Unnamed(Index { index, span })
+                    } else {
+                        Member::Named(syn::parse_str::<Ident>(&arg).map_err(|_| {
+                            Error::new(span, format!("invalid field `{}` in `display`", arg))
+                        })?)
+                    };
+
+                    let Some(field) = fields.iter().find(|f| f.member == member) else {
+                        return Err(Error::new(
+                            span,
+                            format!("unknown field `{}` in `display`", arg),
+                        ));
+                    };
+
+                    let binding = match &member {
+                        Member::Named(ident) => ident.clone(),
+                        Member::Unnamed(index) => format_ident!("__field{}", index.index),
+                    };
+                    if !bindings.iter().any(|(m, _)| *m == member) {
+                        bindings.push((member, binding.clone()));
+                    }
+
+                    uses.push((field.ty.clone(), FmtTrait::from_spec(&spec)));
+
+                    out.push('{');
+                    out.push_str(&binding.to_string());
+                    out.push_str(&spec);
+                    out.push('}');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push_str("}}");
+                }
+                c => out.push(c),
+            }
+        }
+
+        Ok(Formatted {
+            fmt: LitStr::new(&out, span),
+            bindings,
+            uses,
+        })
+    }
+}
@@ -1,11 +1,14 @@
 mod ast;
 mod attr;
+mod dsl;
 mod expand;
+mod fmt;
+mod located_from;
 
 use proc_macro::TokenStream;
 use syn::{DeriveInput, parse_macro_input};
 
-#[proc_macro_derive(StackError, attributes(source, stack_error, location))]
+#[proc_macro_derive(StackError, attributes(source, stack_error, location, from))]
 pub fn derive_stack_error(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let result = ast::Input::from_input(&input).and_then(expand::expand);
@@ -14,3 +17,24 @@ pub fn derive_stack_error(input: TokenStream) -> TokenStream {
         Err(error) => error.into_compile_error().into(),
     }
 }
+
+/// Declares a set of error types, each deriving `StackError` with a generated `Display`,
+/// `core::error::Error`, `From` conversion from its source and a `new` constructor.
+#[proc_macro]
+pub fn stack_errors(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as dsl::StackErrors);
+    dsl::expand(input).into()
+}
+
+/// Makes `from` of an `impl From<T> for Type` block `#[track_caller]` and binds
+/// `location: &'static Location<'static>` to the caller at the start of its body.
+#[proc_macro_attribute]
+pub fn located_from(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = proc_macro2::TokenStream::from(input.clone());
+    let result = syn::parse::<syn::ItemImpl>(input)
+        .and_then(|input| located_from::expand(args.into(), input));
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(error) => located_from::error_with_item(error, item).into(),
+    }
+}
@@ -0,0 +1,66 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Error, ImplItem, ItemImpl, Result, parse_quote, spanned::Spanned};
+
+/// Makes `from` in an `impl From<T> for Type` block `#[track_caller]` and binds the caller
+/// location to `location`, so `?` conversions report where they happened.
+pub fn expand(args: TokenStream, mut input: ItemImpl) -> Result<TokenStream> {
+    if !args.is_empty() {
+        return Err(Error::new_spanned(
+            args,
+            "`#[located_from]` does not take arguments",
+        ));
+    }
+
+    let is_from = match &input.trait_ {
+        Some((None, path, _)) => path.segments.last().is_some_and(|s| s.ident == "From"),
+        _ => false,
+    };
+    if !is_from {
+        let span = match &input.trait_ {
+            Some((_, path, _)) => path.span(),
+            None => input.self_ty.span(),
+        };
+        return Err(Error::new(
+            span,
+            "`#[located_from]` is only supported on an `impl From<T> for Type` block",
+        ));
+    }
+
+    let Some(from) = input.items.iter_mut().find_map(|item| match item {
+        ImplItem::Fn(f) if f.sig.ident == "from" => Some(f),
+        _ => None,
+    }) else {
+        return Err(Error::new(
+            input.impl_token.span,
+            "`#[located_from]` requires the impl to define `from`",
+        ));
+    };
+
+    if !from
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("track_caller"))
+    {
+        from.attrs.push(parse_quote!(#[track_caller]));
+    }
+    from.block.stmts.insert(
+        0,
+        parse_quote! {
+            #[allow(unused_variables)]
+            let location: &'static ::core::panic::Location<'static> =
+                ::core::panic::Location::caller();
+        },
+    );
+
+    Ok(input.into_token_stream())
+}
+
+/// Keeps the original item next to the error so the rest of the crate still sees the impl.
+pub fn error_with_item(error: Error, item: TokenStream) -> TokenStream {
+    let error = error.into_compile_error();
+    quote! {
+        #error
+        #item
+    }
+}
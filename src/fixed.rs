@@ -0,0 +1,293 @@
+//! Fixed-capacity chain snapshots that need no allocator.
+//!
+//! [ChainSnapshot] copies up to `N` entries of a [Chain] into inline buffers, truncating each
+//! message to `MSG` bytes. It can be created in a `const` context, so firmware can keep the last
+//! failure in static memory and report it later.
+//!
+//! # Examples
+//! ```
+//! # extern crate std;
+//! use pseudo_backtrace::{FrameKind, LocatedError, fixed::ChainSnapshot};
+//!
+//! let mut last_failure: ChainSnapshot<4, 8> = ChainSnapshot::new();
+//!
+//! let error = LocatedError::from(std::io::Error::other("sensor timed out"));
+//! last_failure.record(&error);
+//! drop(error);
+//!
+//! let frame = &last_failure.frames()[0];
+//! assert_eq!(frame.message(), "sensor t");
+//! assert!(frame.is_truncated());
+//! assert_eq!(frame.kind(), FrameKind::Stacked);
+//! assert!(frame.location().is_some());
+//! ```
+
+use crate::{Chain, FrameKind};
+
+/// One layer of a [ChainSnapshot], holding at most `MSG` bytes of its message.
+#[derive(Clone, Copy)]
+pub struct Frame<const MSG: usize> {
+    message: [u8; MSG],
+    len: usize,
+    truncated: bool,
+    location: Option<&'static core::panic::Location<'static>>,
+    kind: FrameKind,
+}
+
+impl<const MSG: usize> Frame<MSG> {
+    const EMPTY: Self = Frame {
+        message: [0; MSG],
+        len: 0,
+        truncated: false,
+        location: None,
+        kind: FrameKind::Std,
+    };
+
+    fn record(&mut self, chain: &Chain<'_>) {
+        use core::fmt::Write as _;
+
+        *self = Self::EMPTY;
+        self.location = chain.location();
+        self.kind = chain.kind();
+        // a full buffer stops formatting with `fmt::Error`, which only means "truncated" here
+        let _ = write!(self, "{}", chain.inner());
+    }
+
+    /// Returns the `Display` output of the recorded error, cut to at most `MSG` bytes on a `char` boundary.
+    pub fn message(&self) -> &str {
+        core::str::from_utf8(&self.message[..self.len]).unwrap_or_default()
+    }
+
+    /// Returns `true` when the message did not fit into `MSG` bytes.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the recorded source location, which only [FrameKind::Stacked] frames have.
+    pub const fn location(&self) -> Option<&'static core::panic::Location<'static>> {
+        self.location
+    }
+
+    /// Returns whether this frame was a [StackError](crate::StackError).
+    pub const fn kind(&self) -> FrameKind {
+        self.kind
+    }
+}
+
+// the bytes past `len` are left over from earlier records, so only the message is compared
+impl<const MSG: usize> PartialEq for Frame<MSG> {
+    fn eq(&self, other: &Self) -> bool {
+        self.message() == other.message()
+            && self.truncated == other.truncated
+            && self.location == other.location
+            && self.kind == other.kind
+    }
+}
+
+impl<const MSG: usize> Eq for Frame<MSG> {}
+
+impl<const MSG: usize> core::fmt::Debug for Frame<MSG> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Frame")
+            .field("message", &self.message())
+            .field("truncated", &self.truncated)
+            .field("location", &self.location)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl<const MSG: usize> core::fmt::Write for Frame<MSG> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let available = MSG - self.len;
+        if s.len() <= available {
+            self.message[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            return Ok(());
+        }
+
+        let mut end = available;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.message[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        self.truncated = true;
+        Err(core::fmt::Error)
+    }
+}
+
+impl<const MSG: usize> core::fmt::Display for Frame<MSG> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{}, at {}", self.message(), location),
+            None => f.write_str(self.message()),
+        }
+    }
+}
+
+/// A copy of up to `N` entries of an error chain, with messages cut to `MSG` bytes.
+///
+/// Unlike [ChainWriter](crate::ChainWriter) it does not borrow the error, and unlike the
+/// allocating snapshot it lives entirely inline. It is displayed like
+/// [ChainWriter](crate::ChainWriter).
+#[derive(Clone, Copy)]
+pub struct ChainSnapshot<const N: usize, const MSG: usize> {
+    frames: [Frame<MSG>; N],
+    len: usize,
+    truncated: bool,
+}
+
+impl<const N: usize, const MSG: usize> ChainSnapshot<N, MSG> {
+    /// Creates an empty snapshot.
+    pub const fn new() -> Self {
+        ChainSnapshot {
+            frames: [Frame::EMPTY; N],
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    /// Replaces the contents with the first `N` entries of `chain`.
+    pub fn record<'a>(&mut self, chain: impl Into<Chain<'a>>) {
+        self.len = 0;
+        self.truncated = false;
+        for chain in chain.into() {
+            let Some(frame) = self.frames.get_mut(self.len) else {
+                self.truncated = true;
+                break;
+            };
+            frame.record(&chain);
+            self.len += 1;
+        }
+    }
+
+    /// Removes every recorded frame.
+    pub const fn clear(&mut self) {
+        self.len = 0;
+        self.truncated = false;
+    }
+
+    /// Returns the recorded frames, starting from the top of the chain.
+    pub fn frames(&self) -> &[Frame<MSG>] {
+        &self.frames[..self.len]
+    }
+
+    /// Returns `true` when nothing has been recorded.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` when the chain had more than `N` entries.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+// the frames past `len` are left over from earlier records, so only the recorded ones are compared
+impl<const N: usize, const MSG: usize> PartialEq for ChainSnapshot<N, MSG> {
+    fn eq(&self, other: &Self) -> bool {
+        self.frames() == other.frames() && self.truncated == other.truncated
+    }
+}
+
+impl<const N: usize, const MSG: usize> Eq for ChainSnapshot<N, MSG> {}
+
+impl<const N: usize, const MSG: usize> core::fmt::Debug for ChainSnapshot<N, MSG> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ChainSnapshot")
+            .field("frames", &self.frames())
+            .field("truncated", &self.truncated)
+            .finish()
+    }
+}
+
+impl<const N: usize, const MSG: usize> Default for ChainSnapshot<N, MSG> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, const N: usize, const MSG: usize> From<Chain<'a>> for ChainSnapshot<N, MSG> {
+    fn from(chain: Chain<'a>) -> Self {
+        let mut snapshot = Self::new();
+        snapshot.record(chain);
+        snapshot
+    }
+}
+
+impl<const N: usize, const MSG: usize> core::fmt::Display for ChainSnapshot<N, MSG> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, frame) in self.frames().iter().enumerate() {
+            writeln!(f, "{}: {}", i, frame)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::ChainSnapshot;
+    use crate::tests::{NestedStd, Stacked};
+    use crate::{Chain, StackErrorExt};
+
+    #[test]
+    fn fixed_snapshot_truncates() {
+        use std::string::ToString;
+
+        let a = std::io::Error::other("Error Ä");
+        let b = NestedStd::new(a).nest();
+        let c = Stacked::new(b).stack();
+
+        let snapshot = ChainSnapshot::<8, 64>::from(Chain::from(&c));
+        assert!(!snapshot.is_truncated());
+        assert_eq!(
+            snapshot.to_string(),
+            c.to_chain_with_limit(usize::MAX).to_string()
+        );
+
+        let mut snapshot = ChainSnapshot::<2, 7>::new();
+        snapshot.record(&c);
+        assert!(snapshot.is_truncated());
+        assert_eq!(snapshot.frames().len(), 2);
+        assert_eq!(snapshot.frames()[0].message(), "Stacked");
+        assert!(!snapshot.frames()[0].is_truncated());
+        assert_eq!(snapshot.frames()[1].message(), "Std");
+
+        // `Ä` is two bytes and does not fit after `Error `
+        let mut snapshot = ChainSnapshot::<1, 7>::new();
+        snapshot.record(c.last());
+        assert_eq!(snapshot.frames()[0].message(), "Error ");
+        assert!(snapshot.frames()[0].is_truncated());
+
+        snapshot.clear();
+        assert!(snapshot.is_empty());
+        assert_eq!(snapshot.to_string(), "");
+    }
+
+    #[test]
+    fn fixed_snapshot_ignores_stale_frames() {
+        use std::format;
+
+        let a = std::io::Error::other("Error");
+        let b = NestedStd::new(a).nest();
+        let c = Stacked::new(b).stack();
+
+        let (mut a, mut b) = (ChainSnapshot::<4, 16>::new(), ChainSnapshot::<4, 16>::new());
+        a.record(&c);
+        a.clear();
+        b.clear();
+        assert_eq!(a, b);
+        assert_eq!(format!("{:?}", a), format!("{:?}", b));
+
+        // a shorter record leaves the tail of the longer one behind
+        a.record(&c);
+        a.record(c.last());
+        b.record(c.last());
+        assert_eq!(a, b);
+        assert_eq!(format!("{:?}", a), format!("{:?}", b));
+    }
+}
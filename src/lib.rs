@@ -2,9 +2,18 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
-pub use pseudo_backtrace_derive::StackError;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub use pseudo_backtrace_derive::{StackError, located_from, stack_errors};
+pub mod fixed;
 #[doc(hidden)]
 pub mod private;
+#[cfg(feature = "alloc")]
+mod snapshot;
+
+#[cfg(feature = "alloc")]
+pub use snapshot::{ChainSnapshot, Frame};
 
 /// One layer in a stack of chained errors.
 #[derive(Debug, Clone)]
@@ -19,16 +28,24 @@ impl<'a> Chain<'a> {
     /// Returns lower-level error
     pub fn next(&self) -> Option<Chain<'a>> {
         match self {
-            Chain::Stacked(stack_error) => stack_error.next(),
+            Chain::Stacked(stack_error) => (*stack_error).next(),
             Chain::Std(error) => error.source().map(Chain::Std),
         }
     }
 
     /// Returns the underlying error for this stack layer.
-    pub const fn inner(&'a self) -> &'a dyn core::error::Error {
+    pub const fn inner(&self) -> &'a dyn core::error::Error {
         match self {
-            Chain::Stacked(stack_error) => stack_error,
-            Chain::Std(error) => error,
+            Chain::Stacked(stack_error) => *stack_error,
+            Chain::Std(error) => *error,
+        }
+    }
+
+    /// Returns whether this layer is a [StackError] or a plain [core::error::Error].
+    pub const fn kind(&self) -> FrameKind {
+        match self {
+            Chain::Stacked(_) => FrameKind::Stacked,
+            Chain::Std(_) => FrameKind::Std,
         }
     }
 
@@ -39,6 +56,98 @@ impl<'a> Chain<'a> {
             _ => None,
         }
     }
+
+    /// Creates an iterator starting at this layer.
+    pub fn iter(&self) -> Iter<'a> {
+        self.clone().into_iter()
+    }
+
+    /// Returns a [ChainWriter] starting at this layer. See [StackErrorExt::to_chain].
+    pub fn to_chain(&self) -> ChainWriter<'a> {
+        self.to_chain_with_limit(1)
+    }
+
+    /// Returns a [ChainWriter] starting at this layer, capped to `limit` trailing [core::error::Error] entries. See [StackErrorExt::to_chain_with_limit].
+    pub fn to_chain_with_limit(&self, limit: usize) -> ChainWriter<'a> {
+        ChainWriter {
+            std_limit: limit,
+            stack: self.clone(),
+        }
+    }
+
+    /// Returns an owned [ChainSnapshot] of the entries [to_chain](Chain::to_chain) would print.
+    #[cfg(feature = "alloc")]
+    pub fn snapshot(&self) -> ChainSnapshot {
+        self.snapshot_with_limit(1)
+    }
+
+    /// Returns an owned [ChainSnapshot] of the entries [to_chain_with_limit](Chain::to_chain_with_limit) would print.
+    #[cfg(feature = "alloc")]
+    pub fn snapshot_with_limit(&self, limit: usize) -> ChainSnapshot {
+        ChainSnapshot::new(self.clone(), limit)
+    }
+
+    /// Returns the deepest layer, which is this layer when there is no lower-level error.
+    pub fn last(&self) -> Chain<'a> {
+        self.iter().last().unwrap_or_else(|| self.clone())
+    }
+
+    /// Returns the deepest [StackError] from this layer down, if any.
+    pub fn last_stacked(&self) -> Option<&'a dyn StackError> {
+        self.iter()
+            .filter_map(|e| match e {
+                Chain::Stacked(stack_error) => Some(stack_error),
+                _ => None,
+            })
+            .last()
+    }
+
+    /// Returns the first [core::error::Error] that is not a [StackError] from this layer down, if any.
+    pub fn first_std(&self) -> Option<&'a dyn core::error::Error> {
+        self.iter()
+            .filter_map(|e| match e {
+                Chain::Std(error) => Some(error),
+                _ => None,
+            })
+            .next()
+    }
+}
+
+impl Chain<'static> {
+    /// Returns the error of this layer as `T`, if it is one.
+    ///
+    /// Layers match like in [StackErrorExt::find]: a [`LocatedError<T>`](LocatedError) matches as the `T` it wraps, and references, `Box` and `Arc` are looked through. Downcasting needs `'static` trait objects, so this is only available on a `Chain<'static>`. To search a chain borrowed from an error, use [StackErrorExt::find].
+    ///
+    /// # Examples
+    /// ```
+    /// use pseudo_backtrace::{Chain, StackError};
+    ///
+    /// #[derive(Debug, StackError)]
+    /// #[stack_error(display = "not ready", impl_error)]
+    /// struct NotReady {
+    ///     location: &'static core::panic::Location<'static>,
+    /// }
+    ///
+    /// static NOT_READY: NotReady = NotReady {
+    ///     location: core::panic::Location::caller(),
+    /// };
+    ///
+    /// let chain = Chain::from(&NOT_READY);
+    /// assert!(chain.downcast_ref::<NotReady>().is_some());
+    /// assert!(chain.downcast_ref::<core::fmt::Error>().is_none());
+    /// ```
+    pub fn downcast_ref<T: core::error::Error + 'static>(&self) -> Option<&'static T> {
+        downcast_wrapped(self.inner())
+    }
+}
+
+/// Whether a recorded frame came from a [StackError] or a plain [core::error::Error].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameKind {
+    /// The frame was a [Chain::Stacked].
+    Stacked,
+    /// The frame was a [Chain::Std].
+    Std,
 }
 
 impl core::fmt::Display for Chain<'_> {
@@ -76,7 +185,26 @@ where
     }
 }
 
+/// Location reported by derived [StackError] impls whose `Option` location field is `None`.
+///
+/// It points into this crate rather than into user code. Being a `static`, it has a single
+/// address, so it can be told apart with [core::ptr::eq] as well as by comparing against it
+/// directly.
+///
+/// # Examples
+/// ```
+/// use pseudo_backtrace::UNKNOWN_LOCATION;
+///
+/// assert!(UNKNOWN_LOCATION.file().ends_with("lib.rs"));
+/// assert!(core::ptr::eq(UNKNOWN_LOCATION, UNKNOWN_LOCATION));
+/// ```
+pub static UNKNOWN_LOCATION: &core::panic::Location<'static> = core::panic::Location::caller();
+
 /// Error types that can report a stack trace-like chain.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a `StackError`",
+    note = "derive `StackError` for `{Self}`, mark the field `#[stack_error(std)]` if it only implements `core::error::Error`, or wrap it in `LocatedError<{Self}>`"
+)]
 pub trait StackError: core::error::Error {
     /// Returns the source location of this error.
     fn location(&self) -> &'static core::panic::Location<'static>;
@@ -98,10 +226,7 @@ pub struct Iter<'a> {
 }
 
 impl<'a> Iter<'a> {
-    const fn new<E>(source: &'a E) -> Self
-    where
-        E: StackError,
-    {
+    const fn new(source: &'a dyn StackError) -> Self {
         Iter {
             stack: Some(Chain::Stacked(source)),
         }
@@ -179,6 +304,24 @@ where
     }
 }
 
+/// Compares the inner errors, ignoring where they were recorded.
+///
+/// # Examples
+/// ```
+/// use pseudo_backtrace::LocatedError;
+///
+/// let first = LocatedError::from(core::fmt::Error);
+/// let second = LocatedError::from(core::fmt::Error);
+/// assert_eq!(first, second);
+/// ```
+impl<E: PartialEq> PartialEq for LocatedError<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl<E: Eq> Eq for LocatedError<E> {}
+
 impl<E> From<E> for LocatedError<E> {
     #[track_caller]
     fn from(value: E) -> Self {
@@ -189,6 +332,70 @@ impl<E> From<E> for LocatedError<E> {
     }
 }
 
+impl<E: StackError + ?Sized> StackError for &E {
+    fn location(&self) -> &'static core::panic::Location<'static> {
+        (**self).location()
+    }
+
+    fn next<'a>(&'a self) -> Option<Chain<'a>> {
+        (**self).next()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: StackError> StackError for alloc::boxed::Box<E> {
+    fn location(&self) -> &'static core::panic::Location<'static> {
+        (**self).location()
+    }
+
+    fn next<'a>(&'a self) -> Option<Chain<'a>> {
+        (**self).next()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: StackError + ?Sized> StackError for alloc::sync::Arc<E> {
+    fn location(&self) -> &'static core::panic::Location<'static> {
+        (**self).location()
+    }
+
+    fn next<'a>(&'a self) -> Option<Chain<'a>> {
+        (**self).next()
+    }
+}
+
+/// `core` only implements `Error` for `Box<E>` with a sized `E`, so boxed trait objects get
+/// their own `Error` impl here before they can be a [StackError].
+#[cfg(feature = "alloc")]
+macro_rules! impl_boxed_dyn {
+    ($($bounds:tt)*) => {
+        impl<'b> core::error::Error for alloc::boxed::Box<dyn StackError $($bounds)* + 'b> {
+            fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+                (**self).source()
+            }
+        }
+
+        impl<'b> StackError for alloc::boxed::Box<dyn StackError $($bounds)* + 'b> {
+            fn location(&self) -> &'static core::panic::Location<'static> {
+                (**self).location()
+            }
+
+            fn next<'a>(&'a self) -> Option<Chain<'a>> {
+                (**self).next()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_boxed_dyn!();
+#[cfg(feature = "alloc")]
+impl_boxed_dyn!(+ Send);
+#[cfg(feature = "alloc")]
+impl_boxed_dyn!(+ Send + Sync);
+#[cfg(feature = "alloc")]
+impl_boxed_dyn!(+ Send + Sync + core::panic::UnwindSafe);
+
 /// Helper for display [Chain]
 #[derive(Debug, Clone)]
 pub struct ChainWriter<'a> {
@@ -198,15 +405,7 @@ pub struct ChainWriter<'a> {
 
 impl<'a> core::fmt::Display for ChainWriter<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut std_remaining = self.std_limit;
-        for (i, err) in self.stack.clone().into_iter().enumerate() {
-            if matches!(err, Chain::Std(_)) {
-                if std_remaining == 0 {
-                    break;
-                }
-                std_remaining -= 1;
-            }
-
+        for (i, err) in limit_std(self.stack.clone(), self.std_limit).enumerate() {
             writeln!(f, "{}: {}", i, err)?;
         }
 
@@ -214,6 +413,20 @@ impl<'a> core::fmt::Display for ChainWriter<'a> {
     }
 }
 
+/// Walks `stack`, stopping before the `std_limit + 1`th entry that is not a [StackError].
+fn limit_std<'a>(stack: Chain<'a>, std_limit: usize) -> impl Iterator<Item = Chain<'a>> {
+    let mut std_remaining = std_limit;
+    stack.into_iter().take_while(move |err| {
+        if matches!(err, Chain::Std(_)) {
+            if std_remaining == 0 {
+                return false;
+            }
+            std_remaining -= 1;
+        }
+        true
+    })
+}
+
 /// Convenience helpers for types implementing [StackError].
 pub trait StackErrorExt: StackError + Sized {
     /// Returns a [ChainWriter] that walks this error stack from the top and prints a single trailing non- [StackError] source when formatting.
@@ -252,10 +465,32 @@ pub trait StackErrorExt: StackError + Sized {
     /// // 2: StackError C, at src/main.rs:18:5  
     /// ```
     fn to_chain_with_limit<'a>(&'a self, limit: usize) -> ChainWriter<'a> {
-        ChainWriter {
-            std_limit: limit,
-            stack: Chain::from(self),
-        }
+        Chain::from(self).to_chain_with_limit(limit)
+    }
+
+    /// Returns an owned [ChainSnapshot] of the entries [to_chain](StackErrorExt::to_chain) would print.
+    ///
+    /// ## Example
+    ///
+    /// ```ignore
+    /// let err = StackErrorC::new();
+    /// let snapshot = err.snapshot();
+    /// drop(err);
+    /// println!("{}", snapshot);
+    /// // 0: StackError A, at src/main.rs:20:5
+    /// // 1: StackError B, at src/main.rs:19:5
+    /// // 2: StackError C, at src/main.rs:18:5
+    /// // 3: StdError A
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn snapshot(&self) -> ChainSnapshot {
+        self.snapshot_with_limit(1)
+    }
+
+    /// Returns an owned [ChainSnapshot] capped to `limit` trailing [core::error::Error] entries, like [to_chain_with_limit](StackErrorExt::to_chain_with_limit).
+    #[cfg(feature = "alloc")]
+    fn snapshot_with_limit(&self, limit: usize) -> ChainSnapshot {
+        Chain::from(self).snapshot_with_limit(limit)
     }
 
     /// Returns the deepest [Chain] in the chain.
@@ -273,7 +508,7 @@ pub trait StackErrorExt: StackError + Sized {
     where
         Self: Sized,
     {
-        self.iter().last().unwrap_or(Chain::from(self))
+        Chain::from(self).last()
     }
 
     /// Returns the deepest [StackError] in the chain
@@ -289,13 +524,7 @@ pub trait StackErrorExt: StackError + Sized {
     /// 5: StdError C
     /// ```
     fn last_stacked(&self) -> &dyn StackError {
-        self.iter()
-            .filter_map(|e| match e {
-                Chain::Stacked(stack_error) => Some(stack_error),
-                _ => None,
-            })
-            .last()
-            .unwrap_or(self)
+        Chain::from(self).last_stacked().unwrap_or(self)
     }
 
     /// Returns the first [core::error::Error] in the chain
@@ -311,29 +540,222 @@ pub trait StackErrorExt: StackError + Sized {
     /// 5: StdError C
     /// ```
     fn first_std(&self) -> Option<&dyn core::error::Error> {
-        self.iter()
-            .filter_map(|e| match e {
-                Chain::Std(error) => Some(error),
-                _ => None,
-            })
-            .next()
+        Chain::from(self).first_std()
+    }
+
+    /// Returns the first error of type `T` in the chain, together with the nearest recorded location at or above it.
+    ///
+    /// Both [StackError] and [core::error::Error] layers are searched, and a [`LocatedError<T>`](LocatedError) layer matches as the `T` it wraps. Downcasting needs a `'static` view of each layer, which is the error itself for the top layer and the parent's [source](core::error::Error::source) for the others, so a layer that [StackError::next] reaches without `source()` returning it is skipped.
+    ///
+    /// ## Example
+    ///
+    /// ```ignore
+    /// 0: StackError A, at src/main.rs:20:5
+    /// 1: StackError B, at src/main.rs:19:5 <- location
+    /// 2: StdError A <- find::<StdErrorA>()
+    /// ```
+    fn find<T>(&self) -> Option<(&T, &'static core::panic::Location<'static>)>
+    where
+        Self: 'static,
+        T: core::error::Error + 'static,
+    {
+        find(self)
+    }
+
+    /// Returns `true` when [find](StackErrorExt::find) would find an error of type `T`.
+    fn is<T>(&self) -> bool
+    where
+        Self: 'static,
+        T: core::error::Error + 'static,
+    {
+        self.find::<T>().is_some()
     }
 }
 
+/// Walks the chain from `top`, downcasting each layer below it through the `'static` view its
+/// parent's `source()` gives of it.
+fn find<'a, T>(
+    top: &'a (dyn StackError + 'static),
+) -> Option<(&'a T, &'static core::panic::Location<'static>)>
+where
+    T: core::error::Error + 'static,
+{
+    let mut location = top.location();
+    if let Some(found) = downcast_wrapped(top) {
+        return Some((found, location));
+    }
+    let mut view = top.source();
+    for layer in Iter::new(top).skip(1) {
+        if let Some(layer_location) = layer.location() {
+            location = layer_location;
+        }
+        // a hand-written `source()` may return the `Box` or `Arc` the layer is held in, which has
+        // another address than the layer itself
+        if let Some(found) = view.and_then(downcast_wrapped) {
+            return Some((found, location));
+        }
+        view = layer.inner().source();
+    }
+    None
+}
+
+/// Downcasts one layer to `T`, or to the `T` in a [LocatedError<T>], which is not a layer of its own.
+fn downcast_layer<'a, T>(view: &'a (dyn core::error::Error + 'static)) -> Option<&'a T>
+where
+    T: core::error::Error + 'static,
+{
+    if let Some(found) = view.downcast_ref::<T>() {
+        return Some(found);
+    }
+    view.downcast_ref::<LocatedError<T>>()
+        .map(|located| &located.source)
+}
+
+/// Downcasts a layer, looking through the references and smart pointers that implement
+/// [StackError] by forwarding to the error they point to.
+fn downcast_wrapped<'a, T>(view: &'a (dyn core::error::Error + 'static)) -> Option<&'a T>
+where
+    T: core::error::Error + 'static,
+{
+    if let Some(found) = downcast_layer(view) {
+        return Some(found);
+    }
+    if let Some(found) = view.downcast_ref::<&'static T>() {
+        return Some(*found);
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        if let Some(found) = view.downcast_ref::<alloc::boxed::Box<T>>() {
+            return Some(found);
+        }
+        if let Some(found) = view.downcast_ref::<alloc::sync::Arc<T>>() {
+            return Some(found);
+        }
+
+        macro_rules! downcast_dyn {
+            ($($bounds:tt)*) => {
+                if let Some(boxed) =
+                    view.downcast_ref::<alloc::boxed::Box<dyn StackError $($bounds)* + 'static>>()
+                {
+                    return downcast_wrapped(&**boxed);
+                }
+                if let Some(shared) =
+                    view.downcast_ref::<alloc::sync::Arc<dyn StackError $($bounds)* + 'static>>()
+                {
+                    return downcast_wrapped(&**shared);
+                }
+            };
+        }
+
+        downcast_dyn!();
+        downcast_dyn!(+ Send);
+        downcast_dyn!(+ Send + Sync);
+        downcast_dyn!(+ Send + Sync + core::panic::UnwindSafe);
+    }
+
+    None
+}
+
 impl<E: StackError> StackErrorExt for E {}
 
+/// Gives `dyn StackError` trait objects the helpers of [StackErrorExt], which needs `Sized`.
+///
+/// [StackError::iter] cannot be shadowed on the trait object itself, so iteration goes through
+/// `IntoIterator` for the reference instead.
+macro_rules! impl_dyn_stack_error {
+    ($($bounds:tt)*) => {
+        impl<'a> From<&'a (dyn StackError $($bounds)* + 'a)> for Chain<'a> {
+            fn from(stack_error: &'a (dyn StackError $($bounds)* + 'a)) -> Self {
+                Chain::Stacked(stack_error)
+            }
+        }
+
+        impl<'a> IntoIterator for &'a (dyn StackError $($bounds)* + 'a) {
+            type Item = Chain<'a>;
+            type IntoIter = Iter<'a>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                Iter::new(self)
+            }
+        }
+
+        impl dyn StackError $($bounds)* + '_ {
+            /// See [StackErrorExt::to_chain].
+            pub fn to_chain(&self) -> ChainWriter<'_> {
+                Chain::Stacked(self).to_chain()
+            }
+
+            /// See [StackErrorExt::to_chain_with_limit].
+            pub fn to_chain_with_limit(&self, limit: usize) -> ChainWriter<'_> {
+                Chain::Stacked(self).to_chain_with_limit(limit)
+            }
+
+            /// See [StackErrorExt::snapshot].
+            #[cfg(feature = "alloc")]
+            pub fn snapshot(&self) -> ChainSnapshot {
+                Chain::Stacked(self).snapshot()
+            }
+
+            /// See [StackErrorExt::snapshot_with_limit].
+            #[cfg(feature = "alloc")]
+            pub fn snapshot_with_limit(&self, limit: usize) -> ChainSnapshot {
+                Chain::Stacked(self).snapshot_with_limit(limit)
+            }
+
+            /// See [StackErrorExt::last].
+            pub fn last(&self) -> Chain<'_> {
+                Chain::Stacked(self).last()
+            }
+
+            /// See [StackErrorExt::last_stacked].
+            pub fn last_stacked(&self) -> &dyn StackError {
+                Chain::Stacked(self).last_stacked().unwrap_or(self)
+            }
+
+            /// See [StackErrorExt::first_std].
+            pub fn first_std(&self) -> Option<&dyn core::error::Error> {
+                Chain::Stacked(self).first_std()
+            }
+        }
+
+        impl dyn StackError $($bounds)* + 'static {
+            /// See [StackErrorExt::find].
+            pub fn find<T>(&self) -> Option<(&T, &'static core::panic::Location<'static>)>
+            where
+                T: core::error::Error + 'static,
+            {
+                find(self)
+            }
+
+            /// See [StackErrorExt::is].
+            pub fn is<T>(&self) -> bool
+            where
+                T: core::error::Error + 'static,
+            {
+                self.find::<T>().is_some()
+            }
+        }
+    };
+}
+
+impl_dyn_stack_error!();
+impl_dyn_stack_error!(+ Send);
+impl_dyn_stack_error!(+ Send + Sync);
+impl_dyn_stack_error!(+ Send + Sync + core::panic::UnwindSafe);
+
 #[cfg(test)]
 mod tests {
     extern crate std;
 
     use super::{Chain, StackError, StackErrorExt};
     #[derive(Debug)]
-    struct NestedStd {
+    pub(crate) struct NestedStd {
         source: std::boxed::Box<dyn core::error::Error + 'static>,
     }
 
     impl NestedStd {
-        fn new<E>(source: E) -> Self
+        pub(crate) fn new<E>(source: E) -> Self
         where
             E: core::error::Error + 'static,
         {
@@ -342,7 +764,7 @@ mod tests {
             }
         }
 
-        fn nest(self) -> Self {
+        pub(crate) fn nest(self) -> Self {
             Self {
                 source: std::boxed::Box::new(self),
             }
@@ -362,7 +784,7 @@ mod tests {
     }
 
     #[derive(Debug)]
-    enum Stacked {
+    pub(crate) enum Stacked {
         Stacked {
             source: std::boxed::Box<Stacked>,
             location: &'static core::panic::Location<'static>,
@@ -374,7 +796,7 @@ mod tests {
     }
 
     impl Stacked {
-        fn new<E>(source: E) -> Self
+        pub(crate) fn new<E>(source: E) -> Self
         where
             E: core::error::Error + 'static,
         {
@@ -385,7 +807,7 @@ mod tests {
         }
 
         #[track_caller]
-        fn stack(self) -> Self {
+        pub(crate) fn stack(self) -> Self {
             Self::Stacked {
                 source: std::boxed::Box::new(self),
                 location: core::panic::Location::caller(),
@@ -447,4 +869,46 @@ mod tests {
         std::println!("to_chain_with_limit: {stack}");
         assert_eq!(stack.lines().count(), 7);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn snapshot_matches_chain() {
+        use crate::{ChainSnapshot, FrameKind};
+        use std::string::ToString;
+
+        fn assert_owned<T: Send + Sync + 'static>() {}
+        assert_owned::<ChainSnapshot>();
+
+        let a = std::io::Error::other("Error A");
+        let b = NestedStd::new(a).nest();
+        let c = Stacked::new(b).stack();
+
+        let snapshot = c.snapshot();
+        assert_eq!(snapshot.to_string(), c.to_chain().to_string());
+        assert_eq!(snapshot.frames().len(), 3);
+
+        let second = c.iter().nth(1).unwrap();
+        assert_eq!(second.snapshot().frames().len(), 2);
+
+        let snapshot = c.snapshot_with_limit(usize::MAX);
+        assert_eq!(
+            snapshot.to_string(),
+            c.to_chain_with_limit(usize::MAX).to_string()
+        );
+        let kinds = snapshot.frames().iter().map(|frame| frame.kind());
+        assert!(kinds.eq([
+            FrameKind::Stacked,
+            FrameKind::Stacked,
+            FrameKind::Std,
+            FrameKind::Std,
+            FrameKind::Std
+        ]));
+        assert_eq!(snapshot.frames()[0].location(), Some(c.location()));
+        assert_eq!(snapshot.frames()[4].message(), "Error A");
+        assert_eq!(snapshot.frames()[4].location(), None);
+
+        let copy = snapshot.clone();
+        drop(c);
+        assert_eq!(copy, snapshot);
+    }
 }
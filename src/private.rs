@@ -1,8 +1,40 @@
-use core::panic::UnwindSafe;
+use core::panic::{Location, UnwindSafe};
 
-use crate::StackError;
+use crate::{Chain, StackError};
 
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
+pub use alloc::boxed::Box;
+
+/// Emits the wrapper generated by `#[stack_error(boxed)]`, which needs `Box`.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pseudo_backtrace_boxed {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+/// Reports `#[stack_error(boxed)]` at the attribute instead of at an unresolved `Box`.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pseudo_backtrace_boxed {
+    ($($item:tt)*) => {
+        ::core::compile_error!("`boxed` requires the `alloc` feature of `pseudo-backtrace`");
+    };
+}
+
+#[doc(hidden)]
+pub use crate::__pseudo_backtrace_boxed as boxed;
+
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a std source",
+    label = "this source does not implement `core::error::Error`",
+    note = "implement `core::error::Error` for `{Self}`, or mark the field `#[stack_error(skip)]` if it is not an error"
+)]
 pub trait AsDynStdError<'a>: SealedStd {
     fn as_dyn_std_error(&self) -> &(dyn core::error::Error + 'a);
 }
@@ -43,6 +75,11 @@ impl<'a> AsDynStdError<'a> for dyn core::error::Error + Send + Sync + UnwindSafe
 }
 
 #[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a stacked source",
+    label = "this source does not implement `StackError`",
+    note = "mark the field `#[stack_error(std)]` if `{Self}` only implements `core::error::Error`, or store `LocatedError<{Self}>` to record where it was created"
+)]
 pub trait AsDynStackError<'a>: SealedStack {
     fn as_dyn_stack_error(&self) -> &(dyn StackError + 'a);
 }
@@ -82,6 +119,91 @@ impl<'a> AsDynStackError<'a> for dyn StackError + Send + Sync + UnwindSafe + 'a
     }
 }
 
+/// Field types that a derived [StackError::location] can read the location from.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the location of a `StackError`",
+    label = "expected `&'static Location<'static>`, `Option<&'static Location<'static>>` or a `StackError`",
+    note = "store the location as `&'static Location<'static>`, or wrap the source in `LocatedError` to record it there"
+)]
+pub trait AsLocation {
+    fn as_location(&self) -> &'static Location<'static>;
+}
+
+impl AsLocation for &'static Location<'static> {
+    #[inline]
+    fn as_location(&self) -> &'static Location<'static> {
+        self
+    }
+}
+
+impl AsLocation for Option<&'static Location<'static>> {
+    #[inline]
+    fn as_location(&self) -> &'static Location<'static> {
+        self.unwrap_or(crate::UNKNOWN_LOCATION)
+    }
+}
+
+impl<T: StackError + ?Sized> AsLocation for T {
+    #[inline]
+    fn as_location(&self) -> &'static Location<'static> {
+        self.location()
+    }
+}
+
+/// Borrows a stacked field as `dyn StackError`.
+///
+/// The derive calls this with the field type and the field's span, so that a type that is not a
+/// [StackError] is reported on the field rather than on the derive.
+#[doc(hidden)]
+#[inline]
+pub fn as_stacked<'a, 'b, T: AsDynStackError<'a> + ?Sized>(
+    value: &'b T,
+) -> &'b (dyn StackError + 'a) {
+    value.as_dyn_stack_error()
+}
+
+/// Borrows a std field as `dyn Error`, like [as_stacked].
+#[doc(hidden)]
+#[inline]
+pub fn as_std<'a, 'b, T: AsDynStdError<'a> + ?Sized>(
+    value: &'b T,
+) -> &'b (dyn core::error::Error + 'a) {
+    value.as_dyn_std_error()
+}
+
+/// Borrows a source whose kind was inferred as `dyn Error`, like [as_stacked].
+#[doc(hidden)]
+#[inline]
+pub fn as_source<'a, 'b, T: AutoSource<'a> + ?Sized>(
+    value: &'b T,
+) -> &'b (dyn core::error::Error + 'a) {
+    value.as_dyn_source()
+}
+
+/// Location field types that derived constructors and `From` impls can fill with the caller.
+#[doc(hidden)]
+pub trait FromCaller {
+    #[track_caller]
+    fn from_caller() -> Self;
+}
+
+impl FromCaller for &'static Location<'static> {
+    #[inline]
+    #[track_caller]
+    fn from_caller() -> Self {
+        Location::caller()
+    }
+}
+
+impl FromCaller for Option<&'static Location<'static>> {
+    #[inline]
+    #[track_caller]
+    fn from_caller() -> Self {
+        Some(Location::caller())
+    }
+}
+
 #[doc(hidden)]
 pub trait SealedStd {}
 impl<T: core::error::Error> SealedStd for T {}
@@ -97,3 +219,261 @@ impl SealedStack for dyn StackError + '_ {}
 impl SealedStack for dyn StackError + Send + '_ {}
 impl SealedStack for dyn StackError + Send + Sync + '_ {}
 impl SealedStack for dyn StackError + Send + Sync + UnwindSafe + '_ {}
+
+/// Autoref-specialization fallback for source fields whose kind the derive could not infer.
+///
+/// `(&AutoChain(value)).auto_chain()` resolves to [AutoStacked] when `T: StackError`, and to
+/// [AutoStd] otherwise.
+#[doc(hidden)]
+pub struct AutoChain<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait AutoStacked<'a> {
+    fn auto_chain(&self) -> Chain<'a>;
+}
+
+impl<'a, T: StackError + 'a> AutoStacked<'a> for AutoChain<'a, T> {
+    #[inline]
+    fn auto_chain(&self) -> Chain<'a> {
+        Chain::Stacked(self.0)
+    }
+}
+
+#[doc(hidden)]
+pub trait AutoStd<'a, T: ?Sized> {
+    fn auto_chain(&self) -> Chain<'a>
+    where
+        T: AutoSource<'a>;
+}
+
+impl<'a, T: ?Sized> AutoStd<'a, T> for &AutoChain<'a, T> {
+    #[inline]
+    fn auto_chain(&self) -> Chain<'a>
+    where
+        T: AutoSource<'a>,
+    {
+        Chain::Std(self.0.as_dyn_source())
+    }
+}
+
+/// Types usable as a source without a `#[stack_error(..)]` attribute.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the next error in the stack",
+    label = "the derive could not tell whether this source is stacked or std",
+    note = "implement `StackError` or `core::error::Error` for `{Self}`, or mark the field with `#[stack_error(stacked)]`, `#[stack_error(std)]` or `#[stack_error(skip)]`"
+)]
+pub trait AutoSource<'a> {
+    fn as_dyn_source(&self) -> &(dyn core::error::Error + 'a);
+}
+
+impl<'a, T: core::error::Error + 'a> AutoSource<'a> for T {
+    #[inline]
+    fn as_dyn_source(&self) -> &(dyn core::error::Error + 'a) {
+        self
+    }
+}
+
+/// Writes the whole chain like [ChainWriter](crate::ChainWriter), without the newline after the
+/// last entry, for the derived `Debug`.
+#[doc(hidden)]
+pub fn debug_chain(error: &dyn StackError, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    for (i, err) in Chain::Stacked(error).into_iter().enumerate() {
+        if i > 0 {
+            f.write_str("\n")?;
+        }
+        write!(f, "{}: {}", i, err)?;
+    }
+
+    Ok(())
+}
+
+/// Compares two std error chains by the `Display` output of every layer.
+#[doc(hidden)]
+pub fn std_eq(mut a: &dyn core::error::Error, mut b: &dyn core::error::Error) -> bool {
+    loop {
+        if !display_eq(a, b) {
+            return false;
+        }
+        match (a.source(), b.source()) {
+            (Some(next_a), Some(next_b)) => (a, b) = (next_a, next_b),
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Compares two stacks layer by layer, by kind and `Display` output, ignoring locations.
+#[doc(hidden)]
+pub fn stack_eq(a: &dyn StackError, b: &dyn StackError) -> bool {
+    let mut a = Some(Chain::Stacked(a));
+    let mut b = Some(Chain::Stacked(b));
+    loop {
+        match (a, b) {
+            (Some(Chain::Stacked(x)), Some(Chain::Stacked(y))) if display_eq(x, y) => {
+                (a, b) = (x.next(), y.next());
+            }
+            (Some(Chain::Std(x)), Some(Chain::Std(y))) => return std_eq(x, y),
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Compares the `Display` output of two values.
+///
+/// `a` is formatted once into a `String`, and `b` is checked against it in a single pass.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub fn display_eq(a: &dyn core::fmt::Display, b: &dyn core::fmt::Display) -> bool {
+    use alloc::string::ToString;
+
+    let a = a.to_string();
+    Window::matches(b, 0, &a) == Some(a.len())
+}
+
+/// Compares the `Display` output of two values without allocating.
+///
+/// Every chunk written by `a` is checked against the same range of `b`, so `b` is formatted once
+/// per chunk.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+pub fn display_eq(a: &dyn core::fmt::Display, b: &dyn core::fmt::Display) -> bool {
+    struct Cursor<'a> {
+        other: &'a dyn core::fmt::Display,
+        offset: usize,
+    }
+
+    impl core::fmt::Write for Cursor<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.offset + s.len();
+            if Window::matches(self.other, self.offset, s).is_none_or(|len| len < end) {
+                return Err(core::fmt::Error);
+            }
+            self.offset = end;
+            Ok(())
+        }
+    }
+
+    let mut cursor = Cursor {
+        other: b,
+        offset: 0,
+    };
+    core::fmt::write(&mut cursor, format_args!("{}", a)).is_ok()
+        && Window::matches(b, cursor.offset, "") == Some(cursor.offset)
+}
+
+/// Checks one range of a value's `Display` output.
+struct Window<'a> {
+    start: usize,
+    expected: &'a [u8],
+    position: usize,
+}
+
+impl Window<'_> {
+    /// Returns the output length if the output at `start` begins with `expected`.
+    fn matches(value: &dyn core::fmt::Display, start: usize, expected: &str) -> Option<usize> {
+        let mut window = Window {
+            start,
+            expected: expected.as_bytes(),
+            position: 0,
+        };
+        core::fmt::write(&mut window, format_args!("{}", value)).ok()?;
+        Some(window.position)
+    }
+}
+
+impl core::fmt::Write for Window<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let chunk = s.as_bytes();
+        let low = self.position.max(self.start);
+        let high = (self.position + chunk.len()).min(self.start + self.expected.len());
+        if low < high
+            && chunk[low - self.position..high - self.position]
+                != self.expected[low - self.start..high - self.start]
+        {
+            return Err(core::fmt::Error);
+        }
+        self.position += chunk.len();
+        Ok(())
+    }
+}
+
+/// Autoref-specialization for comparing source fields whose kind the derive could not infer.
+///
+/// `(&AutoEq(a, b)).auto_eq()` resolves to [AutoPartialEq] when `T: PartialEq`, and to
+/// [AutoStdEq] otherwise.
+#[doc(hidden)]
+pub struct AutoEq<'a, T: ?Sized>(pub &'a T, pub &'a T);
+
+#[doc(hidden)]
+pub trait AutoPartialEq {
+    fn auto_eq(&self) -> bool;
+}
+
+impl<T: PartialEq + ?Sized> AutoPartialEq for AutoEq<'_, T> {
+    #[inline]
+    fn auto_eq(&self) -> bool {
+        self.0 == self.1
+    }
+}
+
+#[doc(hidden)]
+pub trait AutoStdEq<'a, T: ?Sized> {
+    fn auto_eq(&self) -> bool
+    where
+        T: AutoSource<'a>;
+}
+
+impl<'a, T: ?Sized> AutoStdEq<'a, T> for &AutoEq<'a, T> {
+    #[inline]
+    fn auto_eq(&self) -> bool
+    where
+        T: AutoSource<'a>,
+    {
+        std_eq(self.0.as_dyn_source(), self.1.as_dyn_source())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::display_eq;
+
+    #[test]
+    fn display_eq_chunks() {
+        struct Chunks(&'static [&'static str]);
+
+        impl core::fmt::Display for Chunks {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.iter().try_for_each(|chunk| f.write_str(chunk))
+            }
+        }
+
+        assert!(display_eq(&Chunks(&["ab", "c"]), &"abc"));
+        assert!(display_eq(&"abc", &Chunks(&["a", "", "bc"])));
+        assert!(display_eq(&Chunks(&[]), &""));
+        assert!(!display_eq(&Chunks(&["ab", "d"]), &"abc"));
+        assert!(!display_eq(&"ab", &Chunks(&["a", "bc"])));
+        assert!(!display_eq(&"abc", &"ab"));
+        assert!(!display_eq(&"", &"a"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display_eq_formats_once() {
+        use core::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl core::fmt::Display for Counted<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.set(self.0.get() + 1);
+                (0..64).try_for_each(|_| f.write_str("chunk"))
+            }
+        }
+
+        let (a, b) = (Cell::new(0), Cell::new(0));
+        assert!(display_eq(&Counted(&a), &Counted(&b)));
+        assert_eq!((a.get(), b.get()), (1, 1));
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::{string::String, string::ToString, sync::Arc, vec::Vec};
+
+use crate::{Chain, FrameKind};
+
+/// One owned layer of a [ChainSnapshot].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    message: String,
+    location: Option<&'static core::panic::Location<'static>>,
+    kind: FrameKind,
+}
+
+impl Frame {
+    fn new(chain: &Chain<'_>) -> Self {
+        Frame {
+            message: chain.inner().to_string(),
+            location: chain.location(),
+            kind: chain.kind(),
+        }
+    }
+
+    /// Returns the `Display` output of the error this frame was recorded from.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the recorded source location, which only [FrameKind::Stacked] frames have.
+    pub const fn location(&self) -> Option<&'static core::panic::Location<'static>> {
+        self.location
+    }
+
+    /// Returns whether this frame was a [StackError](crate::StackError).
+    pub const fn kind(&self) -> FrameKind {
+        self.kind
+    }
+}
+
+impl core::fmt::Display for Frame {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{}, at {}", self.message, location),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// An owned copy of an error chain that outlives the error it was taken from.
+///
+/// Created by [StackErrorExt::snapshot](crate::StackErrorExt::snapshot). It is `Send + Sync + 'static`, and cloning it only bumps a reference count, so it can be kept after the error is dropped, sent to another thread or queued for logging. It is displayed like [ChainWriter](crate::ChainWriter).
+///
+/// # Examples
+/// ```
+/// # extern crate std;
+/// use pseudo_backtrace::{FrameKind, LocatedError, StackErrorExt};
+///
+/// let error = LocatedError::from(std::io::Error::other("disk full"));
+/// let snapshot = error.snapshot();
+/// drop(error);
+///
+/// let frames = snapshot.frames();
+/// assert_eq!(frames.len(), 1);
+/// assert_eq!(frames[0].message(), "disk full");
+/// assert_eq!(frames[0].kind(), FrameKind::Stacked);
+///
+/// std::thread::spawn(move || snapshot.to_string()).join().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSnapshot {
+    frames: Arc<[Frame]>,
+}
+
+impl ChainSnapshot {
+    pub(crate) fn new(stack: Chain<'_>, std_limit: usize) -> Self {
+        let frames: Vec<Frame> = crate::limit_std(stack, std_limit)
+            .map(|chain| Frame::new(&chain))
+            .collect();
+        ChainSnapshot {
+            frames: frames.into(),
+        }
+    }
+
+    /// Returns the recorded frames, starting from the top of the chain.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+impl core::fmt::Display for ChainSnapshot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, frame) in self.frames.iter().enumerate() {
+            writeln!(f, "{}: {}", i, frame)?;
+        }
+
+        Ok(())
+    }
+}
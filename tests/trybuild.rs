@@ -3,4 +3,12 @@ fn trybuild_ui() {
     let t = trybuild::TestCases::new();
     t.pass("tests/trybuild/pass/*.rs");
     t.compile_fail("tests/trybuild/fail/*.rs");
+    // these name `alloc`-only types, or print diagnostics that list them
+    #[cfg(feature = "alloc")]
+    {
+        t.pass("tests/trybuild/alloc/pass/*.rs");
+        t.compile_fail("tests/trybuild/alloc/fail/*.rs");
+    }
+    #[cfg(not(feature = "alloc"))]
+    t.compile_fail("tests/trybuild/no_alloc/fail/*.rs");
 }
@@ -0,0 +1,9 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "wrong location type", impl_error)]
+struct WrongLocationType {
+    location: u32,
+}
+
+fn main() {}
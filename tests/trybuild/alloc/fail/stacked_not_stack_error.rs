@@ -0,0 +1,11 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "stacked io", impl_error)]
+struct StackedIo {
+    #[stack_error(stacked)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+use pseudo_backtrace::{Chain, StackError, StackErrorExt};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf", impl_error, constructors, from, boxed)]
+pub struct LeafInner {
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app", impl_error, constructors, kind, boxed = AppError)]
+pub enum App {
+    Leaf {
+        #[from]
+        source: Leaf,
+        location: &'static core::panic::Location<'static>,
+    },
+    Parse {
+        #[from]
+        source: core::num::ParseIntError,
+        location: &'static core::panic::Location<'static>,
+    },
+    Timeout(u64, #[location] &'static core::panic::Location<'static>),
+}
+
+fn read() -> Result<(), Leaf> {
+    Err(std::io::Error::other("io"))?
+}
+
+fn app() -> Result<(), AppError> {
+    read()?;
+    Ok(())
+}
+
+fn parse() -> Result<u32, AppError> {
+    Ok("x".parse::<u32>()?)
+}
+
+fn main() {
+    assert_eq!(
+        core::mem::size_of::<Result<(), AppError>>(),
+        core::mem::size_of::<usize>()
+    );
+
+    let leaf = read().unwrap_err();
+    assert_eq!(leaf.location().line(), 27);
+    assert_eq!(leaf.to_string(), "leaf");
+    assert!(matches!(leaf.next(), Some(Chain::Std(_))));
+    assert!(core::error::Error::source(&leaf).is_some());
+    assert!(format!("{:?}", leaf).starts_with("LeafInner {"));
+
+    let err = app().unwrap_err();
+    assert_eq!(err.location().line(), 31);
+    assert_eq!(err.kind(), AppKind::Leaf);
+    assert!(matches!(err.inner(), App::Leaf { .. }));
+    assert_eq!(err.iter().count(), 3);
+    assert_eq!(err.to_chain().to_string().lines().count(), 3);
+
+    let err = parse().unwrap_err();
+    assert_eq!(err.location().line(), 36);
+    assert_eq!(err.kind(), AppKind::Parse);
+
+    let err = AppError::from(App::timeout(30));
+    assert!(matches!(err.into_inner(), App::Timeout(30, _)));
+}
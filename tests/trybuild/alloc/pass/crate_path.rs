@@ -0,0 +1,52 @@
+//! The derive used through a re-export, as an error-facade crate would provide it.
+
+mod facade {
+    pub use pseudo_backtrace::*;
+}
+
+use facade::StackError;
+
+#[derive(StackError)]
+#[stack_error(crate = facade, display = "leaf", impl_error, constructors, debug, eq)]
+struct Leaf {
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(StackError)]
+#[stack_error(
+    crate = "crate::facade",
+    display = "app",
+    impl_error,
+    debug,
+    kind,
+    partial_eq,
+    boxed = App
+)]
+enum AppInner<T> {
+    Leaf {
+        #[from]
+        source: Leaf,
+        location: Option<&'static core::panic::Location<'static>>,
+    },
+    Generic {
+        source: T,
+        location: &'static core::panic::Location<'static>,
+    },
+    #[stack_error(transparent)]
+    Transparent(Leaf),
+}
+
+fn main() {
+    let app: App<Leaf> = Leaf::new(std::io::Error::other("io")).into();
+    assert_eq!(app.kind(), AppInnerKind::Leaf);
+    assert_eq!(app.iter().count(), 3);
+    assert_eq!(format!("{:?}", app).lines().count(), 3);
+
+    let generic = |message: &str| AppInner::Generic {
+        source: Leaf::new(std::io::Error::other(message.to_owned())),
+        location: core::panic::Location::caller(),
+    };
+    assert!(generic("io") == generic("io"));
+    assert!(generic("io") != generic("disk"));
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use pseudo_backtrace::{LocatedError, StackError, StackErrorExt};
+
+#[derive(Debug, PartialEq)]
+struct Timeout(u64);
+
+impl core::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "timed out after {}s", self.0)
+    }
+}
+
+impl core::error::Error for Timeout {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "request failed", impl_error, constructors)]
+struct Request {
+    source: LocatedError<Timeout>,
+    location: &'static core::panic::Location<'static>,
+}
+
+// `source()` returns the box rather than the error in it
+#[derive(Debug, StackError)]
+#[stack_error(display = "handler failed", constructors)]
+struct Handler {
+    source: Box<Request>,
+    location: &'static core::panic::Location<'static>,
+}
+
+impl core::error::Error for Handler {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn request(seconds: u64) -> Request {
+    Request::new(Timeout(seconds).into())
+}
+
+fn main() {
+    // wrappers forward to the error they hold, which is the top layer
+    let boxed = Box::new(request(5));
+    let (top, _) = boxed.find::<Request>().unwrap();
+    assert!(core::ptr::eq(top, &*boxed));
+    assert_eq!(boxed.find::<Timeout>().map(|(t, _)| t.0), Some(5));
+
+    let shared = Arc::new(request(5));
+    let (top, _) = shared.find::<Request>().unwrap();
+    assert!(core::ptr::eq(top, &*shared));
+
+    let object: Box<dyn StackError + Send + Sync> = Box::new(request(5));
+    assert!(object.is::<Request>());
+    let shared: Arc<dyn StackError + Send + Sync> = Arc::new(request(5));
+    assert!(shared.is::<Request>());
+
+    let handler = Handler::new(Box::new(request(2)));
+    let (found, _) = handler.find::<Request>().unwrap();
+    assert!(core::ptr::eq(found, &*handler.source));
+    assert_eq!(handler.find::<Timeout>().map(|(t, _)| t.0), Some(2));
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use pseudo_backtrace::{Chain, LocatedError, StackError, StackErrorExt};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "task failed", impl_error, constructors)]
+struct TaskError {
+    source: LocatedError<std::io::Error>,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn task() -> TaskError {
+    TaskError::new(LocatedError::from(std::io::Error::other("io")))
+}
+
+fn main() {
+    let boxed: Box<dyn StackError + Send + Sync> = Box::new(task());
+    assert_eq!(boxed.to_chain().to_string(), task_chain(&boxed));
+    assert_eq!(boxed.iter().count(), 2);
+    assert!(matches!(Chain::from(&*boxed), Chain::Stacked(_)));
+    assert!(core::error::Error::source(&boxed).is_some());
+
+    let error = Arc::new(task());
+    let shared = Arc::clone(&error);
+    let count = std::thread::spawn(move || shared.iter().count())
+        .join()
+        .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(Chain::Stacked(&error).location(), Some(error.location()));
+
+    let by_ref = &*error;
+    assert_eq!(by_ref.last_stacked().to_string(), "io");
+
+    let boxed = Box::new(task());
+    assert_eq!(boxed.location(), (*boxed).location());
+}
+
+fn task_chain(error: &impl StackErrorExt) -> String {
+    error.to_chain().to_string()
+}
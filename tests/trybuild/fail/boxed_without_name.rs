@@ -0,0 +1,9 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "boxed without name", boxed)]
+struct BoxedWithoutName {
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
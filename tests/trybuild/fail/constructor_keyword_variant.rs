@@ -0,0 +1,14 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "module error", impl_error, constructors)]
+enum ModuleError {
+    Crate {
+        location: &'static core::panic::Location<'static>,
+    },
+    Type {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
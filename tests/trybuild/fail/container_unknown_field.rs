@@ -0,0 +1,10 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(location = meta, next = cause)]
+struct ContainerUnknownField {
+    cause: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
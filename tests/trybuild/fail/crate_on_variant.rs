@@ -0,0 +1,12 @@
+use pseudo_backtrace::StackError;
+
+#[derive(StackError)]
+#[stack_error(display = "crate on variant")]
+enum CrateOnVariant {
+    #[stack_error(crate = pseudo_backtrace)]
+    First {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pseudo_backtrace::StackError;
+
+#[derive(StackError)]
+#[stack_error(display = "debug on variant")]
+enum DebugOnVariant {
+    #[stack_error(debug)]
+    First {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+enum MissingVariant {
+    #[stack_error(display = "first")]
+    First {
+        location: &'static core::panic::Location<'static>,
+    },
+    Second {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
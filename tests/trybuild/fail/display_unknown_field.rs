@@ -0,0 +1,9 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "missing {path}")]
+struct UnknownField {
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
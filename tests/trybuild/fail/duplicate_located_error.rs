@@ -0,0 +1,10 @@
+use pseudo_backtrace::{LocatedError, StackError};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "retry")]
+struct Retry {
+    last: LocatedError<std::io::Error>,
+    first: LocatedError<std::io::Error>,
+}
+
+fn main() {}
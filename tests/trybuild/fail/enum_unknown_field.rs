@@ -0,0 +1,15 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(location = at, next = cuase)]
+enum EnumUnknownField {
+    Io {
+        cause: std::io::Error,
+        at: &'static core::panic::Location<'static>,
+    },
+    Shutdown {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "ratio {ratio}", impl_error, eq)]
+struct NotTotal {
+    ratio: f64,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
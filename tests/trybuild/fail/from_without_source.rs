@@ -0,0 +1,11 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+enum FromWithoutSource {
+    #[stack_error(from)]
+    First {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
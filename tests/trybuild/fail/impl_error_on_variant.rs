@@ -0,0 +1,11 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+enum ImplErrorOnVariant {
+    #[stack_error(impl_error)]
+    First {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+struct InvalidLocationArg {
+    #[location(foo)]
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use pseudo_backtrace::located_from;
+
+struct Read;
+
+#[located_from]
+impl Default for Read {
+    fn default() -> Self {
+        Read
+    }
+}
+
+struct Load;
+
+#[located_from]
+impl Load {
+    fn from(_: Read) -> Self {
+        Load
+    }
+}
+
+#[located_from(caller)]
+impl From<Read> for Load {
+    fn from(_: Read) -> Self {
+        Load
+    }
+}
+
+fn main() {}
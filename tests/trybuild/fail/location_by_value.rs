@@ -0,0 +1,8 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+struct LocationByValue {
+    location: core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "too large", impl_error, max_size = 32)]
+struct TooLarge {
+    buffer: [u8; 64],
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "too large", impl_error, max_size = 32)]
+enum TooLargeEnum {
+    Small(#[location] &'static core::panic::Location<'static>),
+    Large {
+        buffer: [u8; 64],
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pseudo_backtrace::StackError;
+
+#[derive(StackError)]
+#[stack_error(display = "generic", max_size = 32)]
+struct Generic<T> {
+    value: T,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pseudo_backtrace::StackError;
+
+#[derive(StackError)]
+#[stack_error(display = "partial_eq on variant")]
+enum PartialEqOnVariant {
+    #[stack_error(partial_eq)]
+    First {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "skip with source")]
+struct SkipWithSource {
+    #[source]
+    #[stack_error(skip)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use pseudo_backtrace::stack_errors;
+
+stack_errors! {
+    pub struct Open {
+        pub path: String,
+    }: std std::io::Error => "opening {path:?}";
+}
+
+fn open() -> Result<(), Open> {
+    Err(std::io::Error::other("missing"))?
+}
+
+fn main() {}
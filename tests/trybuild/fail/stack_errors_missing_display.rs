@@ -0,0 +1,5 @@
+pseudo_backtrace::stack_errors! {
+    pub struct Read: std std::io::Error;
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+pseudo_backtrace::stack_errors! {
+    pub struct Timeout {
+        secs: u64,
+        location: &'static core::panic::Location<'static>,
+    } => "timed out";
+
+    pub struct Read {
+        source: std::io::Error,
+    } => "read failed";
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug)]
+struct NotAnError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "std not error", impl_error)]
+struct StdNotError {
+    #[stack_error(std)]
+    source: NotAnError,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "unknown source kind")]
+struct UnknownSourceKind {
+    source: String,
+    location: &'static core::panic::Location<'static>,
+}
+
+impl core::error::Error for UnknownSourceKind {}
+
+fn main() {}
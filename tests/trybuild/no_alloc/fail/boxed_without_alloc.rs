@@ -0,0 +1,9 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app error", impl_error, boxed)]
+pub struct AppErrorInner {
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+use pseudo_backtrace::{LocatedError, StackError};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "named {path}", impl_error, constructors)]
+pub struct Named {
+    path: &'static str,
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "tuple {0}", impl_error, constructors)]
+pub struct Tuple(u16, #[location] &'static core::panic::Location<'static>);
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "enum", impl_error, constructors)]
+pub enum Enum {
+    NotFound {
+        path: &'static str,
+        location: &'static core::panic::Location<'static>,
+    },
+    IOError(#[source] Named, #[location] &'static core::panic::Location<'static>),
+    Type {
+        location: &'static core::panic::Location<'static>,
+    },
+    Located(LocatedError<std::io::Error>),
+}
+
+fn main() {
+    let line = line!() + 1;
+    let named = Named::new("/tmp", std::io::Error::other("io"));
+    assert_eq!(named.to_string(), "named /tmp");
+    assert_eq!(named.location().line(), line);
+
+    let line = line!() + 1;
+    let tuple = Tuple::new(404);
+    assert_eq!(tuple.to_string(), "tuple 404");
+    assert_eq!(tuple.location().line(), line);
+
+    let line = line!() + 1;
+    let not_found = Enum::not_found("/tmp");
+    assert_eq!(not_found.location().line(), line);
+
+    let line = line!() + 1;
+    let io = Enum::io_error(named);
+    assert_eq!(io.location().line(), line);
+
+    let line = line!() + 1;
+    let ty = Enum::r#type();
+    assert_eq!(ty.location().line(), line);
+
+    let line = line!() + 1;
+    let located = LocatedError::from(std::io::Error::other("io"));
+    let located = Enum::located(located);
+    assert_eq!(located.location().line(), line);
+}
@@ -0,0 +1,58 @@
+use core::panic::Location;
+
+use pseudo_backtrace::{Chain, StackError};
+
+#[derive(Debug)]
+struct Plain;
+
+impl core::fmt::Display for Plain {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "plain")
+    }
+}
+
+impl core::error::Error for Plain {}
+
+// `source` and `location` are ordinary data here, as with serde or thiserror conventions
+#[derive(Debug, StackError)]
+#[stack_error(display = "io", impl_error, constructors, location = meta, next = cause)]
+struct Io {
+    cause: std::io::Error,
+    meta: &'static Location<'static>,
+    source: String,
+    location: (u32, u32),
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app", impl_error, constructors, location = at, next = cause, kind = std)]
+enum App {
+    // `Plain` would be inferred anyway, `kind = std` skips the inference
+    Plain {
+        cause: Plain,
+        at: &'static Location<'static>,
+    },
+    #[stack_error(kind = stacked)]
+    Io {
+        cause: Io,
+        at: &'static Location<'static>,
+    },
+    #[stack_error(next = 0, location = 1)]
+    Tuple(std::io::Error, &'static Location<'static>),
+    // no `cause` field, so the variant has no next error
+    Leaf {
+        at: &'static Location<'static>,
+    },
+}
+
+fn main() {
+    let io = Io::new(std::io::Error::other("io"), "file".into(), (1, 2));
+    assert_eq!(io.location().line(), 48);
+    assert!(matches!(io.next(), Some(Chain::Std(_))));
+
+    assert!(matches!(App::plain(Plain).next(), Some(Chain::Std(_))));
+    assert!(matches!(App::io(io).next(), Some(Chain::Stacked(_))));
+    let tuple = App::tuple(std::io::Error::other("io"));
+    assert_eq!(tuple.location().line(), 54);
+    assert!(matches!(tuple.next(), Some(Chain::Std(_))));
+    assert!(App::leaf().next().is_none());
+}
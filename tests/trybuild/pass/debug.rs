@@ -0,0 +1,53 @@
+use pseudo_backtrace::{StackError, StackErrorExt};
+
+#[derive(StackError)]
+#[stack_error(display = "leaf", impl_error, constructors, debug)]
+struct Leaf {
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(StackError)]
+#[stack_error(display = "app", impl_error, constructors, debug)]
+enum App {
+    Leaf {
+        source: Leaf,
+        location: &'static core::panic::Location<'static>,
+    },
+    Tuple(#[source] Leaf, #[location] &'static core::panic::Location<'static>),
+}
+
+#[derive(StackError)]
+#[stack_error(display = "generic", impl_error, debug)]
+struct Generic<T> {
+    source: T,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn main() {
+    let app = App::leaf(Leaf::new(std::io::Error::other("io")));
+    let debug = format!("{:?}", app);
+    assert_eq!(debug, app.to_chain_with_limit(usize::MAX).to_string().trim_end());
+    assert_eq!(debug.lines().count(), 3);
+    assert!(debug.lines().next().unwrap().starts_with("0: app, at "));
+
+    let pretty = format!("{:#?}", app);
+    assert!(pretty.starts_with("Leaf {\n    source: Leaf {\n"));
+    assert!(pretty.contains("location: Location {"));
+
+    let tuple = App::tuple(Leaf::new(std::io::Error::other("io")));
+    assert!(format!("{:#?}", tuple).starts_with("Tuple(\n"));
+
+    let generic = Generic {
+        source: tuple,
+        location: core::panic::Location::caller(),
+    };
+    assert_eq!(format!("{:?}", generic).lines().count(), 4);
+    assert!(format!("{:#?}", generic).starts_with("Generic {"));
+
+    // what `unwrap` and `main` print
+    let result: Result<(), App> = Err(app);
+    let result = format!("{:?}", result);
+    assert!(result.starts_with("Err(0: app, at "));
+    assert!(result.ends_with("\n2: io)"));
+}
@@ -0,0 +1,78 @@
+use pseudo_backtrace::StackError;
+
+#[track_caller]
+fn location() -> &'static core::panic::Location<'static> {
+    core::panic::Location::caller()
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "fetching {path}: {source}")]
+struct Fetch {
+    path: &'static str,
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+impl core::error::Error for Fetch {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "tuple {0:?} {{escaped}}")]
+struct Tuple(&'static str, #[location] &'static core::panic::Location<'static>);
+impl core::error::Error for Tuple {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "fallback")]
+enum Service {
+    #[stack_error(display = "not found: {path}")]
+    NotFound {
+        path: &'static str,
+        location: &'static core::panic::Location<'static>,
+    },
+    #[stack_error(display = "tuple {0} at {1}")]
+    Tuple(u32, #[location] &'static core::panic::Location<'static>),
+    Other {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+impl core::error::Error for Service {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "generic: {source}")]
+struct Generic<T> {
+    source: T,
+    location: &'static core::panic::Location<'static>,
+}
+impl<T> core::error::Error for Generic<T> where T: StackError {}
+
+fn main() {
+    let fetch = Fetch {
+        path: "/etc/hosts",
+        source: std::io::Error::other("denied"),
+        location: location(),
+    };
+    assert_eq!(fetch.to_string(), "fetching /etc/hosts: denied");
+
+    let tuple = Tuple("a", location());
+    assert_eq!(tuple.to_string(), "tuple \"a\" {escaped}");
+
+    let not_found = Service::NotFound {
+        path: "/tmp",
+        location: location(),
+    };
+    assert_eq!(not_found.to_string(), "not found: /tmp");
+
+    let loc = location();
+    let tuple = Service::Tuple(3, loc);
+    assert_eq!(tuple.to_string(), format!("tuple 3 at {loc}"));
+
+    let other = Service::Other {
+        location: location(),
+    };
+    assert_eq!(other.to_string(), "fallback");
+
+    let generic = Generic {
+        source: fetch,
+        location: location(),
+    };
+    assert_eq!(generic.to_string(), "generic: fetching /etc/hosts: denied");
+}
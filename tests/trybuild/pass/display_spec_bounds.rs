@@ -0,0 +1,34 @@
+use pseudo_backtrace::{LocatedError, StackError};
+
+#[track_caller]
+fn location() -> &'static core::panic::Location<'static> {
+    core::panic::Location::caller()
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "[{source:>8}]", impl_error)]
+struct Padded<T> {
+    source: T,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(
+    display = "code {0:#x} {0:X} {0:o} {0:b}, ratio {1:e} {1:E} {1:.2}",
+    impl_error
+)]
+struct Numbers<C, R>(C, R, #[location] &'static core::panic::Location<'static>);
+
+fn main() {
+    let padded = Padded {
+        source: LocatedError::from(std::io::Error::other("io")),
+        location: location(),
+    };
+    assert_eq!(padded.to_string(), "[      io]");
+
+    let numbers = Numbers(255u32, 1500.0f64, location());
+    assert_eq!(
+        numbers.to_string(),
+        "code 0xff FF 377 11111111, ratio 1.5e3 1.5E3 1500.00"
+    );
+}
@@ -0,0 +1,65 @@
+use pseudo_backtrace::{Chain, LocatedError, StackError, StackErrorExt};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "outer", impl_error, constructors)]
+struct Outer {
+    source: Inner,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "inner", impl_error, constructors)]
+struct Inner {
+    source: LocatedError<std::num::ParseIntError>,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn outer() -> Outer {
+    let parse = "x".parse::<u32>().unwrap_err();
+    Outer::new(Inner::new(LocatedError::from(parse)))
+}
+
+fn render(error: &dyn StackError) -> String {
+    error.to_chain_with_limit(usize::MAX).to_string()
+}
+
+fn render_shared(error: &(dyn StackError + Send + Sync)) -> usize {
+    error.into_iter().count()
+}
+
+fn main() {
+    let error = outer();
+    let expected = error.to_chain().to_string();
+
+    let object: &dyn StackError = &error;
+    assert_eq!(object.to_chain().to_string(), expected);
+    assert_eq!(render(&error), error.to_chain_with_limit(usize::MAX).to_string());
+    assert_eq!(render_shared(&error), 3);
+    assert!(object.first_std().is_none());
+    assert_eq!(object.last().to_string(), error.last().to_string());
+
+    // the deepest `StackError` can keep walking on its own
+    let last = object.last_stacked();
+    assert_eq!(last.to_string(), "invalid digit found in string");
+    assert_eq!(last.into_iter().count(), 1);
+    for layer in last {
+        assert!(layer.location().is_some());
+    }
+    assert_eq!(last.to_chain().to_string().lines().count(), 1);
+
+    let owned = outer();
+    let sendable: &(dyn StackError + Send) = &owned;
+    assert_eq!(sendable.last_stacked().to_string(), last.to_string());
+
+    let object: &(dyn StackError + Send + Sync + core::panic::UnwindSafe) = &owned;
+    assert_eq!(object.to_chain().to_string(), expected);
+    assert_eq!(object.last_stacked().to_string(), last.to_string());
+    assert_eq!(object.into_iter().count(), 3);
+
+    // any layer of a chain has the same helpers
+    let second: Chain<'_> = error.iter().nth(1).unwrap();
+    assert_eq!(second.iter().count(), 2);
+    assert_eq!(second.to_chain().to_string().lines().count(), 2);
+    assert!(second.last_stacked().is_some());
+    assert!(second.first_std().is_none());
+}
@@ -0,0 +1,90 @@
+use pseudo_backtrace::{LocatedError, StackError, StackErrorExt};
+
+#[derive(Debug, PartialEq)]
+struct Timeout(u64);
+
+impl core::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "timed out after {}s", self.0)
+    }
+}
+
+impl core::error::Error for Timeout {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "request failed", impl_error, constructors)]
+struct Request {
+    source: LocatedError<Timeout>,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app failed", impl_error, constructors)]
+struct App {
+    source: Request,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "read failed", impl_error, constructors)]
+struct Read {
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+// `next` reaches the source, but `Error::source` does not
+#[derive(Debug, StackError)]
+#[stack_error(display = "hidden", constructors)]
+struct Hidden {
+    #[stack_error(std)]
+    source: Timeout,
+    location: &'static core::panic::Location<'static>,
+}
+
+impl core::error::Error for Hidden {}
+
+fn main() {
+    let line = line!() + 1;
+    let timeout = LocatedError::from(Timeout(30));
+    let app = App::new(Request::new(timeout));
+
+    let (found, location) = app.find::<Timeout>().unwrap();
+    assert_eq!(found, &Timeout(30));
+    assert_eq!(location.line(), line);
+
+    let (request, location) = app.find::<Request>().unwrap();
+    assert_eq!(request.to_string(), "request failed");
+    assert_eq!(location, request.location());
+
+    let (top, _) = app.find::<App>().unwrap();
+    assert!(core::ptr::eq(top, &app));
+
+    assert!(app.is::<LocatedError<Timeout>>());
+    assert!(!app.is::<std::io::Error>());
+
+    let boxed: Box<dyn StackError + Send + Sync> = Box::new(app);
+    assert!(boxed.is::<Timeout>());
+    let object: &(dyn StackError + Send + Sync) = &*boxed;
+    assert_eq!(object.find::<Timeout>().map(|(t, _)| t.0), Some(30));
+
+    let line = line!() + 1;
+    let read = Read::new(std::io::Error::other("disk full"));
+    let (io, location) = read.find::<std::io::Error>().unwrap();
+    assert_eq!(io.to_string(), "disk full");
+    assert_eq!(location.line(), line);
+
+    // a chain of a `'static` error can be downcast layer by layer
+    static TIMEOUT: std::sync::LazyLock<Request> =
+        std::sync::LazyLock::new(|| Request::new(LocatedError::from(Timeout(7))));
+    let request: &'static Request = &TIMEOUT;
+    let layers: Vec<_> = request.iter().collect();
+    assert!(layers[0].downcast_ref::<Request>().is_some());
+    assert_eq!(layers[1].downcast_ref::<Timeout>(), Some(&Timeout(7)));
+    assert!(layers[1].downcast_ref::<Request>().is_none());
+
+    let hidden = Hidden::new(Timeout(1));
+    assert_eq!(hidden.iter().count(), 2);
+    assert!(hidden.is::<Hidden>());
+    assert!(!hidden.is::<Timeout>());
+}
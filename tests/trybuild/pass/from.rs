@@ -0,0 +1,66 @@
+use pseudo_backtrace::{LocatedError, StackError};
+
+#[derive(Debug)]
+struct Leaf;
+
+impl core::fmt::Display for Leaf {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("leaf")
+    }
+}
+
+impl core::error::Error for Leaf {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "named", impl_error)]
+struct Named {
+    #[from]
+    #[stack_error(std)]
+    source: Leaf,
+    retries: u32,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "tuple", impl_error, from)]
+struct Tuple(#[source] Named, #[location] &'static core::panic::Location<'static>);
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "enum", impl_error)]
+enum Enum {
+    #[stack_error(from)]
+    Tuple(#[source] Tuple, #[location] &'static core::panic::Location<'static>),
+    Located {
+        #[from]
+        inner: LocatedError<std::io::Error>,
+    },
+}
+
+fn leaf() -> Result<(), Leaf> {
+    Err(Leaf)
+}
+
+fn named() -> Result<(), Named> {
+    leaf()?;
+    Ok(())
+}
+
+fn main() {
+    let named = named().unwrap_err();
+    assert_eq!(named.retries, 0);
+    // the `?` in `named()`
+    assert_eq!(named.location().line(), 44);
+
+    let line = line!() + 1;
+    let tuple = Tuple::from(named);
+    assert_eq!(tuple.location().line(), line);
+
+    let line = line!() + 1;
+    let error: Enum = tuple.into();
+    assert_eq!(error.location().line(), line);
+
+    let line = line!() + 1;
+    let located = LocatedError::from(std::io::Error::other("io"));
+    let error = Enum::from(located);
+    assert_eq!(error.location().line(), line);
+}
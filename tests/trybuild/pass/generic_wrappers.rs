@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use pseudo_backtrace::{Chain, LocatedError, StackError};
+
+type Leaf = LocatedError<std::io::Error>;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "optional", impl_error, constructors)]
+struct Optional<T> {
+    source: Option<T>,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "boxed", impl_error, constructors)]
+struct Boxed<T> {
+    source: Box<T>,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "nested", impl_error, constructors)]
+enum Nested<T, E> {
+    OptionBox {
+        source: Option<Box<T>>,
+        location: &'static core::panic::Location<'static>,
+    },
+    Shared {
+        source: Arc<T>,
+        location: &'static core::panic::Location<'static>,
+    },
+    Std {
+        #[stack_error(std)]
+        source: Option<Arc<E>>,
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "borrowed")]
+struct Borrowed<'a, T> {
+    source: &'a T,
+    location: &'static core::panic::Location<'static>,
+}
+
+impl<T: core::fmt::Debug> core::error::Error for Borrowed<'_, T> {}
+
+fn leaf() -> Leaf {
+    LocatedError::from(std::io::Error::other("leaf"))
+}
+
+fn is_stacked(err: &dyn StackError) -> bool {
+    matches!(err.next(), Some(Chain::Stacked(_)))
+}
+
+fn main() {
+    assert!(is_stacked(&Optional::new(Some(leaf()))));
+    assert!(Optional::<Leaf>::new(None).next().is_none());
+    assert!(is_stacked(&Boxed::new(Box::new(leaf()))));
+
+    let nested = Nested::<_, std::io::Error>::option_box(Some(Box::new(leaf())));
+    assert!(is_stacked(&nested));
+    let nested = Nested::<_, std::io::Error>::shared(Arc::new(leaf()));
+    assert!(is_stacked(&nested));
+    let nested = Nested::<Leaf, _>::std(Some(Arc::new(std::io::Error::other("io"))));
+    assert!(matches!(nested.next(), Some(Chain::Std(_))));
+    assert!(core::error::Error::source(&nested).is_some());
+
+    let inner = leaf();
+    let borrowed = Borrowed {
+        source: &inner,
+        location: core::panic::Location::caller(),
+    };
+    assert!(is_stacked(&borrowed));
+}
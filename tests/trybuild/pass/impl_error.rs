@@ -0,0 +1,107 @@
+use pseudo_backtrace::{Chain, StackError};
+
+#[track_caller]
+fn location() -> &'static core::panic::Location<'static> {
+    core::panic::Location::caller()
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf", impl_error)]
+struct Leaf {
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "boxed", impl_error)]
+struct Boxed {
+    #[stack_error(std)]
+    source: Box<dyn core::error::Error + Send + Sync>,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "optional", impl_error)]
+struct Optional {
+    #[source]
+    inner: Option<Leaf>,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "top", impl_error)]
+enum Top {
+    Optional {
+        source: Optional,
+        location: &'static core::panic::Location<'static>,
+    },
+    Boxed(#[source] Box<Boxed>, #[location] &'static core::panic::Location<'static>),
+    Std(
+        #[stack_error(std)] Option<std::io::Error>,
+        #[location] &'static core::panic::Location<'static>,
+    ),
+    Empty {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "generic", impl_error)]
+struct Generic<T> {
+    source: T,
+    location: &'static core::panic::Location<'static>,
+}
+
+/// Asserts that `Error::source` walks the same objects as `StackError::next`.
+fn assert_same_chain(error: &dyn StackError) {
+    let mut chain = Some(Chain::Stacked(error));
+    let mut std_error: Option<&dyn core::error::Error> = Some(error);
+    while let Some(layer) = chain {
+        let std = std_error.expect("source() ended before next()");
+        let object: *const dyn core::error::Error = match layer {
+            Chain::Stacked(error) => error,
+            Chain::Std(error) => error,
+        };
+        assert!(core::ptr::addr_eq(object, std));
+        chain = layer.next();
+        std_error = std.source();
+    }
+    assert!(std_error.is_none(), "next() ended before source()");
+}
+
+fn main() {
+    let leaf = Leaf {
+        source: std::io::Error::other("io"),
+        location: location(),
+    };
+    let optional = Optional {
+        inner: Some(leaf),
+        location: location(),
+    };
+    let top = Top::Optional {
+        source: optional,
+        location: location(),
+    };
+    assert_same_chain(&top);
+
+    let boxed = Boxed {
+        source: Box::new(std::io::Error::other("boxed")),
+        location: location(),
+    };
+    assert_same_chain(&Top::Boxed(Box::new(boxed), location()));
+    assert_same_chain(&Top::Std(Some(std::io::Error::other("io")), location()));
+    assert_same_chain(&Top::Std(None, location()));
+    assert_same_chain(&Top::Empty {
+        location: location(),
+    });
+
+    let generic = Generic {
+        source: Optional {
+            inner: None,
+            location: location(),
+        },
+        location: location(),
+    };
+    assert_same_chain(&generic);
+}
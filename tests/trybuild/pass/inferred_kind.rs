@@ -0,0 +1,93 @@
+use pseudo_backtrace::{Chain, StackError};
+
+#[derive(Debug)]
+struct Plain;
+
+impl core::fmt::Display for Plain {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "plain")
+    }
+}
+
+impl core::error::Error for Plain {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "io", impl_error, constructors)]
+struct Io {
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+/// Shares its name with a std error, but is a `StackError`.
+#[derive(Debug, StackError)]
+#[stack_error(display = "utf8", impl_error, constructors)]
+struct Utf8Error {
+    source: core::str::Utf8Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "boxed", impl_error, constructors)]
+struct Boxed {
+    source: Box<dyn core::error::Error + Send + Sync>,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "inferred", impl_error, constructors)]
+enum Inferred {
+    Parse {
+        source: core::num::ParseIntError,
+        location: &'static core::panic::Location<'static>,
+    },
+    Fmt {
+        source: Option<core::fmt::Error>,
+        location: &'static core::panic::Location<'static>,
+    },
+    // named types are resolved by the compiler
+    Plain {
+        source: Plain,
+        location: &'static core::panic::Location<'static>,
+    },
+    Stacked {
+        source: Io,
+        location: &'static core::panic::Location<'static>,
+    },
+    Local {
+        source: Utf8Error,
+        location: &'static core::panic::Location<'static>,
+    },
+    MaybeStacked(#[source] Option<Boxed>, #[location] &'static core::panic::Location<'static>),
+}
+
+fn is_std(err: &dyn StackError) -> bool {
+    matches!(err.next(), Some(Chain::Std(_)))
+}
+
+fn is_stacked(err: &dyn StackError) -> bool {
+    matches!(err.next(), Some(Chain::Stacked(_)))
+}
+
+fn main() {
+    assert!(is_std(&Io::new(std::io::Error::other("io"))));
+    assert!(is_std(&Boxed::new("boxed".into())));
+    assert!(is_std(&Inferred::parse("x".parse::<u32>().unwrap_err())));
+    assert!(is_std(&Inferred::fmt(Some(core::fmt::Error))));
+    assert!(is_std(&Inferred::plain(Plain)));
+    assert!(is_stacked(&Inferred::stacked(Io::new(
+        std::io::Error::other("io")
+    ))));
+    let utf8 = core::str::from_utf8(&[0xff]).unwrap_err();
+    assert!(is_std(&Utf8Error::new(utf8)));
+    assert!(is_stacked(&Inferred::local(Utf8Error::new(utf8))));
+    assert!(is_stacked(&Inferred::maybe_stacked(Some(Boxed::new(
+        "boxed".into()
+    )))));
+    assert!(Inferred::maybe_stacked(None).next().is_none());
+
+    let plain = Inferred::plain(Plain);
+    assert_eq!(
+        core::error::Error::source(&plain).unwrap().to_string(),
+        "plain"
+    );
+}
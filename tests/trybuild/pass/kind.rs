@@ -0,0 +1,44 @@
+#![deny(missing_docs)]
+//! `kind` generates documented items.
+
+use std::collections::HashSet;
+
+use pseudo_backtrace::StackError;
+
+/// An error with a generated kind.
+#[derive(Debug, StackError)]
+#[stack_error(display = "app error", impl_error, constructors, kind)]
+pub enum AppError<T> {
+    /// Not found.
+    NotFound {
+        /// Path.
+        path: T,
+        /// Location.
+        location: &'static core::panic::Location<'static>,
+    },
+    /// Timed out.
+    Timeout(u64, #[location] &'static core::panic::Location<'static>),
+    /// Raw identifier.
+    r#Type {
+        /// Location.
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+const KIND: AppErrorKind = AppErrorKind::Timeout;
+
+fn main() {
+    let error = AppError::not_found("/etc/hosts");
+    assert_eq!(error.kind(), AppErrorKind::NotFound);
+    assert_eq!(AppError::<()>::timeout(30).kind(), KIND);
+    assert_eq!(AppError::<()>::r#type().kind(), AppErrorKind::Type);
+
+    let kinds = [AppErrorKind::NotFound, AppErrorKind::Timeout, AppErrorKind::NotFound]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(kinds.len(), 2);
+
+    assert_eq!(AppErrorKind::NotFound.to_string(), "NotFound");
+    assert_eq!(AppErrorKind::Type.name(), "Type");
+    assert_eq!(format!("{:?}", AppErrorKind::Timeout), "Timeout");
+}
@@ -0,0 +1,65 @@
+use pseudo_backtrace::{LocatedError, StackError, located_from};
+
+#[derive(Debug)]
+struct Io;
+
+impl core::fmt::Display for Io {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("io")
+    }
+}
+
+impl core::error::Error for Io {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "read failed", impl_error)]
+struct Read {
+    #[stack_error(std)]
+    source: Io,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[located_from]
+impl From<Io> for Read {
+    fn from(source: Io) -> Self {
+        Read { source, location }
+    }
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "load failed", impl_error)]
+struct Load {
+    source: LocatedError<Read>,
+}
+
+// `location` may go unused when the body records it some other way
+#[pseudo_backtrace::located_from]
+impl core::convert::From<Read> for Load {
+    #[track_caller]
+    fn from(read: Read) -> Self {
+        Load {
+            source: LocatedError::from(read),
+        }
+    }
+}
+
+fn io() -> Result<(), Io> {
+    Err(Io)
+}
+
+fn read() -> Result<(), Read> {
+    io()?;
+    Ok(())
+}
+
+fn load() -> Result<(), Load> {
+    read()?;
+    Ok(())
+}
+
+fn main() {
+    let error = load().unwrap_err();
+    assert_eq!(error.location().line(), 56);
+    let read = read().unwrap_err();
+    assert_eq!(read.location().line(), 51);
+}
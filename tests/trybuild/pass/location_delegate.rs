@@ -0,0 +1,74 @@
+use pseudo_backtrace::{LocatedError, StackError};
+
+// an alias hides the `LocatedError` name from the derive
+type IoError = LocatedError<std::io::Error>;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf", impl_error, constructors)]
+struct Leaf {
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "alias", impl_error, constructors)]
+struct Alias {
+    #[location]
+    #[source]
+    inner: IoError,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "wrapper", impl_error, constructors)]
+struct Wrapper {
+    // reports where `leaf` was created, but does not chain to it
+    #[location(delegate)]
+    leaf: Leaf,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "delegate", impl_error, constructors)]
+enum Delegate {
+    Boxed {
+        #[location(delegate)]
+        #[source]
+        inner: Box<Leaf>,
+    },
+    Tuple(#[location(delegate)] Leaf, &'static str),
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "generic", impl_error)]
+struct Generic<T> {
+    #[location(delegate)]
+    #[source]
+    inner: T,
+}
+
+fn main() {
+    let located = IoError::from(std::io::Error::other("io"));
+    let location = located.location();
+    let alias = Alias::new(located);
+    assert_eq!(alias.location(), location);
+
+    let leaf = Leaf::new(std::io::Error::other("io"));
+    let location = leaf.location();
+    let wrapper = Wrapper::new(leaf);
+    assert_eq!(wrapper.location(), location);
+    assert!(wrapper.next().is_none());
+
+    let leaf = Leaf::new(std::io::Error::other("io"));
+    let location = leaf.location();
+    let boxed = Delegate::boxed(Box::new(leaf));
+    assert_eq!(boxed.location(), location);
+    assert!(boxed.next().is_some());
+
+    let leaf = Leaf::new(std::io::Error::other("io"));
+    let location = leaf.location();
+    let tuple = Delegate::tuple(leaf, "context");
+    assert_eq!(tuple.location(), location);
+
+    let generic = Generic { inner: tuple };
+    assert_eq!(generic.location(), location);
+}
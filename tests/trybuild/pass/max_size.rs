@@ -0,0 +1,27 @@
+use pseudo_backtrace::StackError;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf", impl_error, max_size = 16)]
+struct Leaf {
+    code: u32,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app error", impl_error, max_size = 32)]
+enum AppError {
+    Leaf {
+        #[location(delegate)]
+        source: Leaf,
+    },
+    Io {
+        source: std::io::Error,
+        location: &'static core::panic::Location<'static>,
+    },
+    Unit(#[location] &'static core::panic::Location<'static>),
+}
+
+fn main() {
+    assert!(core::mem::size_of::<Leaf>() <= 16);
+    assert!(core::mem::size_of::<AppError>() <= 32);
+}
@@ -0,0 +1,39 @@
+use core::panic::Location;
+
+use pseudo_backtrace::{StackError, UNKNOWN_LOCATION};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "parse error", impl_error, constructors)]
+struct ParseError {
+    #[stack_error(std)]
+    #[from]
+    source: core::num::ParseIntError,
+    location: Option<&'static Location<'static>>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app error", impl_error, constructors)]
+enum AppError {
+    Parse(#[source] ParseError, #[location] Option<&'static Location<'static>>),
+}
+
+fn parse(s: &str) -> Result<u32, ParseError> {
+    Ok(s.parse::<u32>()?)
+}
+
+fn main() {
+    let err = parse("x").unwrap_err();
+    assert!(err.location.is_some());
+    assert_eq!(err.location(), err.location.unwrap());
+    assert_eq!(err.location().line(), 21);
+
+    let err = ParseError {
+        source: "x".parse::<u32>().unwrap_err(),
+        location: None,
+    };
+    assert!(core::ptr::eq(err.location(), UNKNOWN_LOCATION));
+
+    let app = AppError::parse(err);
+    assert_eq!(app.location().line(), 36);
+    assert!(!core::ptr::eq(app.location(), UNKNOWN_LOCATION));
+}
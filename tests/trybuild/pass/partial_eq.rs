@@ -0,0 +1,120 @@
+use pseudo_backtrace::{LocatedError, StackError};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf {code}", impl_error, constructors, eq)]
+struct Leaf {
+    code: u32,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "app", impl_error, constructors, partial_eq)]
+enum App {
+    Leaf {
+        source: Leaf,
+        location: &'static core::panic::Location<'static>,
+    },
+    Io(#[source] std::io::Error, #[location] &'static core::panic::Location<'static>),
+    Dyn {
+        source: Box<dyn StackError + Send + Sync>,
+        location: Option<&'static core::panic::Location<'static>>,
+    },
+    Located(LocatedError<std::num::ParseIntError>),
+    #[stack_error(transparent)]
+    Transparent(Leaf),
+}
+
+/// Implements `PartialEq`, so it is compared with it.
+#[derive(Debug, PartialEq)]
+struct Compared(u32);
+
+impl std::fmt::Display for Compared {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("compared")
+    }
+}
+
+impl std::error::Error for Compared {}
+
+/// Does not implement `PartialEq`, so it is compared by its message.
+#[derive(Debug)]
+struct Opaque(&'static str);
+
+impl std::fmt::Display for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl std::error::Error for Opaque {}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "inferred", impl_error, partial_eq)]
+enum Inferred {
+    Compared {
+        source: Compared,
+        location: &'static core::panic::Location<'static>,
+    },
+    Opaque {
+        source: Option<Opaque>,
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "generic", impl_error, eq)]
+struct Generic<T> {
+    source: Option<T>,
+    location: &'static core::panic::Location<'static>,
+}
+
+fn other_line() -> Leaf {
+    Leaf::new(7)
+}
+
+fn main() {
+    assert_eq!(Leaf::new(7), other_line());
+    assert_ne!(Leaf::new(7), Leaf::new(8));
+
+    assert_eq!(App::leaf(Leaf::new(7)), App::leaf(other_line()));
+    assert_ne!(App::leaf(Leaf::new(1)), App::transparent(Leaf::new(1)));
+    assert_eq!(App::transparent(Leaf::new(1)), App::transparent(Leaf::new(1)));
+
+    let io = |message: &str| App::io(std::io::Error::other(message.to_owned()));
+    assert_eq!(io("disk"), io("disk"));
+    assert_ne!(io("disk"), io("network"));
+
+    let boxed = |code| App::Dyn {
+        source: Box::new(Leaf::new(code)),
+        location: None,
+    };
+    assert_eq!(boxed(1), boxed(1));
+    assert_ne!(boxed(1), boxed(2));
+
+    let located = |s: &str| App::Located(s.parse::<u8>().unwrap_err().into());
+    assert_eq!(located("x"), located("y"));
+    assert_ne!(located("x"), located("300"));
+
+    let compared = |code| Inferred::Compared {
+        source: Compared(code),
+        location: core::panic::Location::caller(),
+    };
+    assert_eq!(compared(1), compared(1));
+    assert_ne!(compared(1), compared(2));
+
+    let opaque = |message: Option<&'static str>| Inferred::Opaque {
+        source: message.map(Opaque),
+        location: core::panic::Location::caller(),
+    };
+    assert_eq!(opaque(Some("a")), opaque(Some("a")));
+    assert_ne!(opaque(Some("a")), opaque(Some("ab")));
+    assert_ne!(opaque(Some("a")), opaque(None));
+    assert_ne!(opaque(None), compared(1));
+
+    let generic = |code: Option<u32>| Generic {
+        source: code.map(Leaf::new),
+        location: core::panic::Location::caller(),
+    };
+    assert_eq!(generic(Some(1)), generic(Some(1)));
+    assert_ne!(generic(Some(1)), generic(None));
+}
@@ -0,0 +1,54 @@
+use pseudo_backtrace::{Chain, LocatedError, StackError};
+
+type IoError = LocatedError<std::io::Error>;
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "metadata {source} at {location}", impl_error, constructors)]
+struct Metadata {
+    // plain data that happens to use the conventional names
+    #[stack_error(skip)]
+    source: String,
+    #[stack_error(skip)]
+    location: String,
+    #[location]
+    at: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "retry", impl_error, constructors)]
+struct Retry {
+    // spelled out, since only the `LocatedError` name is detected
+    last: LocatedError<std::io::Error>,
+    #[stack_error(skip)]
+    first: LocatedError<std::io::Error>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "skipped", impl_error, constructors)]
+enum Skipped {
+    Tuple(
+        LocatedError<std::io::Error>,
+        #[stack_error(skip)] LocatedError<std::io::Error>,
+    ),
+}
+
+fn main() {
+    let metadata = Metadata::new("config".into(), "disk".into());
+    assert!(metadata.next().is_none());
+    assert_eq!(metadata.to_string(), "metadata config at disk");
+
+    let first = IoError::from(std::io::Error::other("first"));
+    let last = IoError::from(std::io::Error::other("last"));
+    let location = last.location();
+    let retry = Retry::new(last, first);
+    assert_eq!(retry.location(), location);
+    match retry.next() {
+        Some(Chain::Stacked(next)) => assert_eq!(next.to_string(), "last"),
+        _ => panic!("expected the stacked `last` error"),
+    }
+
+    let first = IoError::from(std::io::Error::other("first"));
+    let location = first.location();
+    let skipped = Skipped::tuple(first, IoError::from(std::io::Error::other("second")));
+    assert_eq!(skipped.location(), location);
+}
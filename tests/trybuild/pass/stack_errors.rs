@@ -0,0 +1,74 @@
+use pseudo_backtrace::{StackError, StackErrorExt, stack_errors};
+
+mod errors {
+    pseudo_backtrace::stack_errors! {
+        /// Reading the config file failed.
+        pub struct ReadConfig: std std::io::Error => "reading config";
+
+        #[derive(Clone)]
+        pub struct Timeout {
+            pub secs: u64,
+        } => "timed out after {secs}s";
+    }
+}
+
+stack_errors! {
+    struct ParseConfig {
+        line: usize,
+    }: std::num::ParseIntError => "parsing line {line}: {source}";
+
+    struct LoadConfig: stacked errors::ReadConfig => "loading config";
+
+    struct App: LoadConfig => "app failed";
+
+    struct Wrapper<E: StackError + 'static>: E => "wrapped";
+
+    #[stack_error(debug, partial_eq)]
+    struct Compared {
+        code: u32,
+    } => "code {code}";
+}
+
+fn read() -> Result<(), errors::ReadConfig> {
+    Err(std::io::Error::other("missing"))?;
+    Ok(())
+}
+
+fn load() -> Result<(), LoadConfig> {
+    read()?;
+    Ok(())
+}
+
+fn app() -> Result<(), App> {
+    load()?;
+    Ok(())
+}
+
+fn main() {
+    let error = app().unwrap_err();
+    assert_eq!(error.to_string(), "app failed");
+    assert_eq!(error.location().line(), 43);
+    assert_eq!(error.iter().count(), 4);
+    let chain = error.to_chain().to_string();
+    assert!(chain.contains("1: loading config, at"));
+    assert!(chain.contains("2: reading config, at"));
+    assert!(chain.contains("3: missing"));
+    assert!(core::error::Error::source(&error).is_some());
+
+    let timeout = errors::Timeout::new(30);
+    assert_eq!(timeout.clone().to_string(), "timed out after 30s");
+    assert_eq!(timeout.secs, 30);
+    assert!(timeout.next().is_none());
+
+    let parse = ParseConfig::new(7, "x".parse::<u32>().unwrap_err());
+    assert_eq!(
+        parse.to_string(),
+        "parsing line 7: invalid digit found in string"
+    );
+
+    let wrapper = Wrapper::from(timeout);
+    assert_eq!(wrapper.iter().count(), 2);
+    assert_eq!(Compared::new(1), Compared::new(1));
+    assert!(format!("{:?}", Compared::new(2)).starts_with("0: code 2, at "));
+    assert!(format!("{:?}", wrapper).starts_with("Wrapper { source: Timeout {"));
+}
@@ -0,0 +1,53 @@
+use pseudo_backtrace::{StackError, StackErrorExt};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf", impl_error, constructors)]
+struct Leaf {
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "top", impl_error)]
+enum Top {
+    #[stack_error(transparent, from)]
+    Leaf(Leaf),
+    #[stack_error(transparent)]
+    Boxed { inner: Box<Leaf> },
+    Other {
+        location: &'static core::panic::Location<'static>,
+    },
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(transparent, display = "{0}", impl_error, constructors)]
+struct Wrapper(Top);
+
+fn main() {
+    let leaf = Leaf::new(std::io::Error::other("io"));
+    let location = leaf.location();
+    let top = Top::from(leaf);
+
+    assert_eq!(top.location(), location);
+    assert_eq!(top.to_string(), "leaf");
+    assert_eq!(top.iter().count(), 2);
+    assert_eq!(
+        top.to_chain().to_string(),
+        format!("0: leaf, at {location}\n1: io\n")
+    );
+    assert_eq!(
+        core::error::Error::source(&top).unwrap().to_string(),
+        "io"
+    );
+
+    let wrapper = Wrapper::new(top);
+    assert_eq!(wrapper.location(), location);
+    assert_eq!(wrapper.to_string(), "leaf");
+    assert_eq!(wrapper.iter().count(), 2);
+
+    let boxed = Top::Boxed {
+        inner: Box::new(Leaf::new(std::io::Error::other("io"))),
+    };
+    assert_eq!(boxed.iter().count(), 2);
+}
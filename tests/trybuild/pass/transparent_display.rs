@@ -0,0 +1,40 @@
+use pseudo_backtrace::{StackError, StackErrorExt};
+
+#[derive(Debug, StackError)]
+#[stack_error(display = "leaf", impl_error, constructors)]
+struct Leaf {
+    #[stack_error(std)]
+    source: std::io::Error,
+    location: &'static core::panic::Location<'static>,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(transparent, impl_error, constructors)]
+struct Wrapper(Leaf);
+
+#[derive(Debug, StackError)]
+#[stack_error(transparent, impl_error)]
+struct Named<T> {
+    inner: T,
+}
+
+#[derive(Debug, StackError)]
+#[stack_error(impl_error)]
+enum Top {
+    #[stack_error(transparent)]
+    Leaf(Leaf),
+}
+
+fn main() {
+    let wrapper = Wrapper::new(Leaf::new(std::io::Error::other("io")));
+    assert_eq!(wrapper.to_string(), "leaf");
+    assert_eq!(wrapper.iter().count(), 2);
+
+    let named = Named {
+        inner: Leaf::new(std::io::Error::other("io")),
+    };
+    assert_eq!(named.to_string(), "leaf");
+
+    let top = Top::Leaf(Leaf::new(std::io::Error::other("io")));
+    assert_eq!(top.to_string(), "leaf");
+}